@@ -1,59 +1,77 @@
 use yew::prelude::*;
 use yew_tou_rs::prelude::*;
 
+#[function_component(ReplayButton)]
+pub fn replay_button() -> Html {
+    let tour = use_tour();
+    let onclick = Callback::from(move |_| tour.restart());
+    html! {
+        <button class="replay-button" {onclick}>{"Replay tour"}</button>
+    }
+}
+
 #[function_component(App)]
 pub fn app() -> Html {
     // Define the steps for the tour
     let steps = vec![
         TourStep {
-            selector: ".app".to_string(),
+            selector: None,
             content: "### Welcome to the tour\n\
                 You can find a full demo on [Konnektoren](https://konnektoren.help)"
                 .to_string(),
+            ..Default::default()
         },
         TourStep {
-            selector: ".h1-step".to_string(),
+            selector: Some(".h1-step".to_string()),
             content: "This is a title".to_string(),
+            ..Default::default()
         },
         TourStep {
-            selector: ".p-step".to_string(),
+            selector: Some(".p-step".to_string()),
             content: "This is a paragraph".to_string(),
+            ..Default::default()
         },
         TourStep {
-            selector: ".button-step".to_string(),
+            selector: Some(".button-step".to_string()),
             content: "This is a button".to_string(),
+            ..Default::default()
         },
         // New step for the right-side element
         TourStep {
-            selector: ".right-side-element".to_string(),
+            selector: Some(".right-side-element".to_string()),
             content: "This element is on the right edge of the screen.".to_string(),
+            ..Default::default()
         },
         TourStep {
-            selector: "footer".to_string(),
+            selector: Some("footer".to_string()),
             content: "This is a footer".to_string(),
+            ..Default::default()
         },
     ];
 
     // Render the main application structure
     html! {
-        <div class="app">
-            // Existing elements
-            <h1 class="h1-step" data-title="h1" data-intro="This is a title">{"Hello, Yew Tou-rs"}</h1>
-            <p class="p-step" data-title="p" data-intro="This is a paragraph">{"This is a simple example of a Yew Tou-rs."}</p>
-            <button class="button-step">{"Click me"}</button>
+        <TourProvider>
+            <div class="app">
+                // Existing elements
+                <h1 class="h1-step" data-title="h1" data-intro="This is a title">{"Hello, Yew Tou-rs"}</h1>
+                <p class="p-step" data-title="p" data-intro="This is a paragraph">{"This is a simple example of a Yew Tou-rs."}</p>
+                <button class="button-step">{"Click me"}</button>
+                <ReplayButton />
 
-            // New element on the right side
-            <div class="right-side-element">
-                {"Right Side Element"}
-            </div>
+                // New element on the right side
+                <div class="right-side-element">
+                    {"Right Side Element"}
+                </div>
 
-            <footer class="footer-step">
-                <a href="https://github.com/chriamue/yew-tou-rs">{"yew-tou-rs"}</a>
-            </footer>
+                <footer class="footer-step">
+                    <a href="https://github.com/chriamue/yew-tou-rs">{"yew-tou-rs"}</a>
+                </footer>
 
-            // Include the Tour component, passing in the defined steps
-            <Tour steps={steps} />
-        </div>
+                // Include the Tour component, passing in the defined steps
+                <Tour steps={steps} />
+            </div>
+        </TourProvider>
     }
 }
 
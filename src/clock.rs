@@ -0,0 +1,136 @@
+//! Time source of the tours.
+//!
+//! Display frequency policies like `DisplayFrequency::Cooldown` depend on the current
+//! time. It is read from a `Clock`, which can be replaced, e.g. by a `ManualClock` in tests.
+
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+
+/// A source of the current time in milliseconds since the Unix epoch.
+pub trait Clock {
+    /// Returns the current time in milliseconds since the Unix epoch.
+    fn now(&self) -> u64;
+}
+
+/// The system clock, `Date.now()` in the browser.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[cfg(target_arch = "wasm32")]
+    fn now(&self) -> u64 {
+        js_sys::Date::now() as u64
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn now(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
+
+/// A clock which only moves when told to.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new(1_000);
+/// clock.advance(500);
+/// assert_eq!(clock.now(), 1_500);
+/// ```
+#[derive(Debug, Default)]
+pub struct ManualClock(Cell<u64>);
+
+impl ManualClock {
+    /// Creates a clock showing the given time.
+    pub fn new(now: u64) -> Self {
+        Self(Cell::new(now))
+    }
+
+    /// Sets the time of the clock.
+    pub fn set(&self, now: u64) {
+        self.0.set(now);
+    }
+
+    /// Moves the clock forward by the given milliseconds.
+    pub fn advance(&self, millis: u64) {
+        self.0.set(self.0.get().saturating_add(millis));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> u64 {
+        self.0.get()
+    }
+}
+
+/// A shared handle to a `Clock`, used to pass it through `TourConfig.clock`.
+///
+/// Handles are equal if they point to the same clock.
+#[derive(Clone)]
+pub struct ClockHandle(Rc<dyn Clock>);
+
+impl ClockHandle {
+    /// Wraps a clock.
+    pub fn new(clock: impl Clock + 'static) -> Self {
+        Self(Rc::new(clock))
+    }
+}
+
+impl Default for ClockHandle {
+    fn default() -> Self {
+        Self::new(SystemClock)
+    }
+}
+
+impl std::ops::Deref for ClockHandle {
+    type Target = dyn Clock;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for ClockHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for ClockHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ClockHandle").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock() {
+        let clock = ManualClock::new(10);
+        assert_eq!(clock.now(), 10);
+        clock.advance(5);
+        assert_eq!(clock.now(), 15);
+        clock.set(3);
+        assert_eq!(clock.now(), 3);
+    }
+
+    #[test]
+    fn test_system_clock_is_after_2020() {
+        assert!(SystemClock.now() > 1_577_836_800_000);
+    }
+
+    #[test]
+    fn test_handles_equal_by_clock() {
+        let clock = ClockHandle::new(ManualClock::default());
+        assert_eq!(clock, clock.clone());
+        assert_ne!(clock, ClockHandle::new(ManualClock::default()));
+    }
+}
@@ -3,17 +3,23 @@ use yew::prelude::*;
 #[derive(Properties, Clone, PartialEq)]
 pub struct Props {
     pub(crate) content: String,
+    #[prop_or_default]
+    pub(crate) max_height: Option<u32>,
 }
 
 #[function_component(Content)]
 pub fn content(props: &Props) -> Html {
     let content = props.content.clone();
+    // Longer content scrolls inside the tooltip, which keeps its overflow visible for the arrow
+    let style = props
+        .max_height
+        .map(|max_height| format!("max-height: {}px; overflow-y: auto;", max_height));
     #[cfg(feature = "markdown")]
     {
         let content = Html::from_html_unchecked(AttrValue::from(markdown::to_html(&content)));
 
         html! {
-            <div class="introjs-tooltiptext">
+            <div class="introjs-tooltiptext" style={style}>
                 {content}
             </div>
         }
@@ -21,7 +27,7 @@ pub fn content(props: &Props) -> Html {
     #[cfg(not(feature = "markdown"))]
     {
         html! {
-            <div class="introjs-tooltiptext">
+            <div class="introjs-tooltiptext" style={style}>
                 {content}
             </div>
         }
@@ -0,0 +1,10 @@
+use yew::prelude::*;
+
+#[function_component(Loading)]
+pub fn loading() -> Html {
+    html! {
+        <div class="introjs-tooltiptext introjs-loading" aria-busy="true">
+            {"Loading…"}
+        </div>
+    }
+}
@@ -1,10 +1,12 @@
 pub mod content;
+pub mod loading;
 pub mod navigation;
 pub mod progress;
 pub mod selection;
 pub mod step_info;
 
 pub use content::Content;
+pub use loading::Loading;
 pub use navigation::Navigation;
 pub use progress::Progress;
 pub use selection::Selection;
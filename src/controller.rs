@@ -0,0 +1,667 @@
+use crate::models::{MissingTargetPolicy, TourEvent};
+use std::rc::Rc;
+use yew::Reducible;
+
+/// How many of the most recent events a controller keeps.
+const EVENT_LOG_SIZE: usize = 16;
+
+/// The state a tour can be in.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum TourState {
+    /// The tour has not been started yet.
+    #[default]
+    NotStarted,
+
+    /// The tour is running and shows the step with the given index.
+    Active(usize),
+
+    /// The user went through all steps of the tour.
+    Completed,
+
+    /// The user closed the tour before reaching the end.
+    Skipped,
+}
+
+/// Where the target element of the current step stands, as seen by the `Tour` component.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub(crate) enum StepTarget {
+    /// The step has no target and floats in the center of the screen.
+    NoTarget,
+
+    /// The target element was found.
+    Found,
+
+    /// The target element is not rendered yet and the step waits for it.
+    Waiting,
+
+    /// The target element could not be found.
+    Missing,
+}
+
+impl StepTarget {
+    /// Returns `true` if the step is actually shown to the user, rather than waiting for
+    /// its target or being left because of the missing target `policy`.
+    pub(crate) fn is_shown(self, policy: MissingTargetPolicy) -> bool {
+        match self {
+            StepTarget::NoTarget | StepTarget::Found => true,
+            StepTarget::Waiting => false,
+            StepTarget::Missing => policy == MissingTargetPolicy::Floating,
+        }
+    }
+}
+
+/// A headless state machine driving a tour.
+///
+/// `TourController` holds the navigation logic of a tour without depending on
+/// the DOM, so tour flows can be tested natively. The `Tour` component drives
+/// a controller and renders its current step.
+///
+/// Every transition is recorded as a `TourEvent`, which the `Tour` component
+/// passes on to `TourConfig.on_event`.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{TourController, TourEvent, TourState};
+///
+/// let mut controller = TourController::new(2);
+/// controller.start();
+/// assert_eq!(controller.state(), TourState::Active(0));
+///
+/// controller.next();
+/// controller.next();
+/// assert_eq!(controller.state(), TourState::Completed);
+///
+/// let events: Vec<_> = controller.events_since(0).cloned().collect();
+/// assert_eq!(
+///     events,
+///     vec![
+///         TourEvent::Started,
+///         TourEvent::Next { from: 0, to: 1 },
+///         TourEvent::Completed,
+///     ]
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TourController {
+    state: TourState,
+    step_count: usize,
+    backwards: bool,
+    events: Vec<TourEvent>,
+    event_count: usize,
+    runs: usize,
+    synced: bool,
+}
+
+impl TourController {
+    /// Creates a controller for a tour with `step_count` steps, which has not been started yet.
+    pub fn new(step_count: usize) -> Self {
+        Self {
+            state: TourState::NotStarted,
+            step_count,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the current state of the tour.
+    pub fn state(&self) -> TourState {
+        self.state
+    }
+
+    /// Returns the number of steps of the tour.
+    pub fn step_count(&self) -> usize {
+        self.step_count
+    }
+
+    /// Returns the index of the current step if the tour is active.
+    pub fn current_step(&self) -> Option<usize> {
+        match self.state {
+            TourState::Active(step) => Some(step),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the tour is currently shown.
+    pub fn is_active(&self) -> bool {
+        matches!(self.state, TourState::Active(_))
+    }
+
+    /// Returns `true` if the current step was reached by going back.
+    pub fn is_moving_backwards(&self) -> bool {
+        self.backwards
+    }
+
+    /// Returns `true` if the tour was completed or skipped.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, TourState::Completed | TourState::Skipped)
+    }
+
+    /// Returns `true` if the current state was taken over with `sync` rather than reached
+    /// in this tour, so it was already reported where it happened.
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Returns how many times the tour was started, to tell the runs of a tour apart.
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    /// Returns the total number of events recorded by this controller.
+    pub fn event_count(&self) -> usize {
+        self.event_count
+    }
+
+    /// Returns the events recorded after the first `count` events.
+    ///
+    /// Only the most recent events are kept, older ones are left out.
+    pub fn events_since(&self, count: usize) -> impl Iterator<Item = &TourEvent> {
+        let new_events = self
+            .event_count
+            .saturating_sub(count)
+            .min(self.events.len());
+        self.events[self.events.len() - new_events..].iter()
+    }
+
+    fn record(&mut self, event: TourEvent) {
+        self.synced = false;
+        if self.events.len() == EVENT_LOG_SIZE {
+            self.events.remove(0);
+        }
+        self.events.push(event);
+        self.event_count += 1;
+    }
+
+    /// Starts the tour at the first step. Does nothing if the tour is already active.
+    ///
+    /// A tour without steps is completed right away.
+    pub fn start(&mut self) {
+        self.start_at(0);
+    }
+
+    /// Starts the tour at the given step, e.g. to resume an interrupted tour.
+    /// Does nothing if the tour is already active.
+    ///
+    /// Steps out of range start the tour at the first step.
+    pub fn start_at(&mut self, step: usize) {
+        if !self.is_active() {
+            self.backwards = false;
+            self.state = TourState::Active(if step < self.step_count { step } else { 0 });
+            self.runs += 1;
+            self.record(TourEvent::Started);
+            if self.step_count == 0 {
+                self.complete();
+            }
+        }
+    }
+
+    /// Starts the tour over at the first step, even if it is already active.
+    pub fn restart(&mut self) {
+        self.stop();
+        self.start();
+    }
+
+    /// Moves to the next step, completing the tour after the last step.
+    pub fn next(&mut self) {
+        if let TourState::Active(step) = self.state {
+            self.backwards = false;
+            if step + 1 < self.step_count {
+                self.state = TourState::Active(step + 1);
+                self.record(TourEvent::Next {
+                    from: step,
+                    to: step + 1,
+                });
+            } else {
+                self.complete();
+            }
+        }
+    }
+
+    /// Moves to the previous step. Does nothing on the first step.
+    pub fn prev(&mut self) {
+        if let TourState::Active(step) = self.state
+            && step > 0
+        {
+            self.backwards = true;
+            self.state = TourState::Active(step - 1);
+            self.record(TourEvent::Prev {
+                from: step,
+                to: step - 1,
+            });
+        }
+    }
+
+    /// Leaves the current step in the direction the tour is moving, e.g. because its target is missing.
+    ///
+    /// Going back from the first step moves forward instead.
+    pub fn skip_step(&mut self) {
+        match self.state {
+            TourState::Active(step) if self.backwards && step > 0 => self.prev(),
+            _ => self.next(),
+        }
+    }
+
+    /// Jumps to the step with the given index.
+    ///
+    /// This also (re)starts a tour which is not active. Indices out of range are ignored.
+    pub fn goto(&mut self, step: usize) {
+        if step >= self.step_count {
+            return;
+        }
+        match self.state {
+            TourState::Active(current) => {
+                self.backwards = step < current;
+                self.record(TourEvent::Jumped {
+                    from: current,
+                    to: step,
+                });
+            }
+            _ => {
+                self.backwards = false;
+                self.runs += 1;
+                self.record(TourEvent::Started);
+            }
+        }
+        self.state = TourState::Active(step);
+    }
+
+    /// Moves from the step `from` to the step `to`, or completes the tour if `to` is `None`.
+    ///
+    /// Does nothing if the tour is not on the step `from` anymore, so transitions
+    /// which were delayed can not move a tour which went on in the meantime.
+    pub fn transition(&mut self, from: usize, to: Option<usize>) {
+        if self.state != TourState::Active(from) {
+            return;
+        }
+        match to {
+            Some(step) if step == from + 1 => self.next(),
+            Some(step) if step + 1 == from => self.prev(),
+            Some(step) => self.goto(step),
+            None => self.complete(),
+        }
+    }
+
+    /// Skips the rest of an active tour.
+    pub fn skip(&mut self) {
+        if let TourState::Active(step) = self.state {
+            self.state = TourState::Skipped;
+            self.record(TourEvent::Skipped { at: step });
+        }
+    }
+
+    /// Completes an active tour.
+    pub fn complete(&mut self) {
+        if self.is_active() {
+            self.state = TourState::Completed;
+            self.record(TourEvent::Completed);
+        }
+    }
+
+    /// Takes over the state of the same tour running elsewhere, e.g. in another browser tab.
+    ///
+    /// Only an active tour follows, to another step or to its end. No events are recorded,
+    /// as they were reported where they happened. Steps out of range are ignored.
+    pub fn sync(&mut self, state: TourState) {
+        let TourState::Active(current) = self.state else {
+            return;
+        };
+        match state {
+            TourState::Active(step) if step >= self.step_count || step == current => {}
+            TourState::Active(step) => {
+                self.backwards = step < current;
+                self.state = state;
+                self.synced = true;
+            }
+            _ => {
+                self.state = state;
+                self.synced = true;
+            }
+        }
+    }
+
+    /// Hides the tour without marking it as completed or skipped, so it can be started again.
+    pub fn stop(&mut self) {
+        self.state = TourState::NotStarted;
+        self.synced = false;
+    }
+
+    /// Changes the number of steps, e.g. when the steps of a running tour are replaced.
+    ///
+    /// An active tour stays on its step if it still exists and moves to the new last step otherwise.
+    pub fn set_step_count(&mut self, step_count: usize) {
+        self.step_count = step_count;
+        if let TourState::Active(step) = self.state
+            && step >= step_count
+        {
+            match step_count {
+                0 => self.complete(),
+                _ => {
+                    self.state = TourState::Active(step_count - 1);
+                    self.synced = false;
+                }
+            }
+        }
+    }
+}
+
+/// Actions which can be dispatched to a `TourController` used as a Yew reducer.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum TourAction {
+    Start,
+    StartAt(usize),
+    Restart,
+    Stop,
+    SetStepCount(usize),
+    Next,
+    Prev,
+    Goto(usize),
+    SkipStep,
+    Transition { from: usize, to: Option<usize> },
+    Skip,
+    Complete,
+    Sync(TourState),
+}
+
+impl Reducible for TourController {
+    type Action = TourAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut controller = (*self).clone();
+        match action {
+            TourAction::Start => controller.start(),
+            TourAction::StartAt(step) => controller.start_at(step),
+            TourAction::Restart => controller.restart(),
+            TourAction::Stop => controller.stop(),
+            TourAction::SetStepCount(step_count) => controller.set_step_count(step_count),
+            TourAction::Next => controller.next(),
+            TourAction::Prev => controller.prev(),
+            TourAction::Goto(step) => controller.goto(step),
+            TourAction::SkipStep => controller.skip_step(),
+            TourAction::Transition { from, to } => controller.transition(from, to),
+            TourAction::Skip => controller.skip(),
+            TourAction::Complete => controller.complete(),
+            TourAction::Sync(state) => controller.sync(state),
+        }
+        controller.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn started(step_count: usize) -> TourController {
+        let mut controller = TourController::new(step_count);
+        controller.start();
+        controller
+    }
+
+    #[test]
+    fn test_new_is_not_started() {
+        let controller = TourController::new(3);
+        assert_eq!(controller.state(), TourState::NotStarted);
+        assert_eq!(controller.current_step(), None);
+        assert!(!controller.is_active());
+        assert!(!controller.is_finished());
+    }
+
+    #[rstest]
+    #[case::with_steps(3, TourState::Active(0))]
+    #[case::without_steps(0, TourState::Completed)]
+    fn test_start(#[case] step_count: usize, #[case] expected: TourState) {
+        assert_eq!(started(step_count).state(), expected);
+    }
+
+    #[rstest]
+    #[case::in_range(2, TourState::Active(2))]
+    #[case::out_of_range(3, TourState::Active(0))]
+    fn test_start_at(#[case] step: usize, #[case] expected: TourState) {
+        let mut controller = TourController::new(3);
+        controller.start_at(step);
+        assert_eq!(controller.state(), expected);
+        assert_eq!(
+            controller.events_since(0).collect::<Vec<_>>(),
+            vec![&TourEvent::Started]
+        );
+        controller.start_at(1);
+        assert_eq!(controller.state(), expected);
+    }
+
+    #[test]
+    fn test_next_until_completed() {
+        let mut controller = started(3);
+        controller.next();
+        assert_eq!(controller.state(), TourState::Active(1));
+        controller.next();
+        assert_eq!(controller.state(), TourState::Active(2));
+        controller.next();
+        assert_eq!(controller.state(), TourState::Completed);
+        assert!(controller.is_finished());
+        controller.next();
+        assert_eq!(controller.state(), TourState::Completed);
+    }
+
+    #[test]
+    fn test_prev() {
+        let mut controller = started(3);
+        controller.prev();
+        assert_eq!(controller.state(), TourState::Active(0));
+        assert!(!controller.is_moving_backwards());
+        controller.goto(2);
+        controller.prev();
+        assert_eq!(controller.state(), TourState::Active(1));
+        assert!(controller.is_moving_backwards());
+        controller.next();
+        assert!(!controller.is_moving_backwards());
+        controller.goto(0);
+        assert!(controller.is_moving_backwards());
+    }
+
+    #[test]
+    fn test_skip_step() {
+        let mut controller = started(4);
+        controller.skip_step();
+        assert_eq!(controller.state(), TourState::Active(1));
+        controller.goto(3);
+        controller.prev();
+        controller.skip_step();
+        assert_eq!(controller.state(), TourState::Active(1));
+        controller.prev();
+        controller.skip_step();
+        assert_eq!(controller.state(), TourState::Active(1));
+        controller.goto(3);
+        controller.skip_step();
+        assert_eq!(controller.state(), TourState::Completed);
+    }
+
+    #[test]
+    fn test_events() {
+        let mut controller = started(3);
+        controller.next();
+        controller.prev();
+        controller.goto(2);
+        controller.transition(2, Some(1));
+        controller.transition(1, Some(2));
+        controller.skip();
+        let events: Vec<_> = controller.events_since(0).cloned().collect();
+        assert_eq!(
+            events,
+            vec![
+                TourEvent::Started,
+                TourEvent::Next { from: 0, to: 1 },
+                TourEvent::Prev { from: 1, to: 0 },
+                TourEvent::Jumped { from: 0, to: 2 },
+                TourEvent::Prev { from: 2, to: 1 },
+                TourEvent::Next { from: 1, to: 2 },
+                TourEvent::Skipped { at: 2 },
+            ]
+        );
+        assert_eq!(controller.event_count(), 7);
+        assert_eq!(
+            controller.events_since(6).collect::<Vec<_>>(),
+            vec![&TourEvent::Skipped { at: 2 }]
+        );
+        assert_eq!(controller.events_since(7).count(), 0);
+    }
+
+    #[test]
+    fn test_stop_records_no_event() {
+        let mut controller = started(3);
+        controller.next();
+        let event_count = controller.event_count();
+        controller.stop();
+        assert_eq!(controller.state(), TourState::NotStarted);
+        assert_eq!(controller.events_since(event_count).count(), 0);
+    }
+
+    #[test]
+    fn test_event_log_is_bounded() {
+        let mut controller = started(2);
+        for _ in 0..EVENT_LOG_SIZE {
+            controller.next();
+            controller.prev();
+        }
+        assert_eq!(controller.event_count(), 2 * EVENT_LOG_SIZE + 1);
+        assert_eq!(controller.events_since(0).count(), EVENT_LOG_SIZE);
+        assert_eq!(
+            controller.events_since(0).last(),
+            Some(&TourEvent::Prev { from: 1, to: 0 })
+        );
+    }
+
+    #[rstest]
+    #[case::forward(1, Some(2), TourState::Active(2))]
+    #[case::backward(1, Some(0), TourState::Active(0))]
+    #[case::complete(1, None, TourState::Completed)]
+    #[case::stale(0, Some(2), TourState::Active(1))]
+    #[case::stale_complete(2, None, TourState::Active(1))]
+    fn test_transition(
+        #[case] from: usize,
+        #[case] to: Option<usize>,
+        #[case] expected: TourState,
+    ) {
+        let mut controller = started(3);
+        controller.next();
+        controller.transition(from, to);
+        assert_eq!(controller.state(), expected);
+    }
+
+    #[rstest]
+    #[case::in_range(TourState::Active(0), 2, TourState::Active(2))]
+    #[case::out_of_range(TourState::Active(1), 3, TourState::Active(1))]
+    #[case::restarts(TourState::Skipped, 1, TourState::Active(1))]
+    fn test_goto(#[case] state: TourState, #[case] step: usize, #[case] expected: TourState) {
+        let mut controller = TourController {
+            state,
+            step_count: 3,
+            ..Default::default()
+        };
+        controller.goto(step);
+        assert_eq!(controller.state(), expected);
+    }
+
+    #[rstest]
+    #[case::other_step(TourState::Active(0), TourState::Active(2), TourState::Active(2))]
+    #[case::out_of_range(TourState::Active(0), TourState::Active(3), TourState::Active(0))]
+    #[case::finished(TourState::Active(1), TourState::Skipped, TourState::Skipped)]
+    #[case::not_active(TourState::Completed, TourState::Active(1), TourState::Completed)]
+    fn test_sync(#[case] state: TourState, #[case] other: TourState, #[case] expected: TourState) {
+        let mut controller = TourController {
+            state,
+            step_count: 3,
+            ..Default::default()
+        };
+        controller.sync(other);
+        assert_eq!(controller.state(), expected);
+        assert_eq!(controller.event_count(), 0);
+    }
+
+    #[test]
+    fn test_synced_until_moved_on() {
+        let mut controller = started(3);
+        assert!(!controller.is_synced());
+        controller.sync(TourState::Active(0));
+        assert!(!controller.is_synced());
+        controller.sync(TourState::Active(1));
+        assert!(controller.is_synced());
+        controller.next();
+        assert!(!controller.is_synced());
+        assert_eq!(
+            controller.events_since(1).collect::<Vec<_>>(),
+            vec![&TourEvent::Next { from: 1, to: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_runs() {
+        let mut controller = started(3);
+        assert_eq!(controller.runs(), 1);
+        controller.next();
+        controller.start();
+        assert_eq!(controller.runs(), 1);
+        controller.restart();
+        assert_eq!(controller.runs(), 2);
+        controller.skip();
+        controller.goto(1);
+        assert_eq!(controller.runs(), 3);
+    }
+
+    #[rstest]
+    #[case::keep_step(TourState::Active(1), 4, TourState::Active(1))]
+    #[case::clamp_step(TourState::Active(2), 2, TourState::Active(1))]
+    #[case::no_steps_left(TourState::Active(0), 0, TourState::Completed)]
+    #[case::not_started(TourState::NotStarted, 0, TourState::NotStarted)]
+    fn test_set_step_count(
+        #[case] state: TourState,
+        #[case] step_count: usize,
+        #[case] expected: TourState,
+    ) {
+        let mut controller = TourController {
+            state,
+            step_count: 3,
+            ..Default::default()
+        };
+        controller.set_step_count(step_count);
+        assert_eq!(controller.state(), expected);
+        assert_eq!(controller.step_count(), step_count);
+    }
+
+    #[rstest]
+    #[case::no_target(StepTarget::NoTarget, MissingTargetPolicy::Skip, true)]
+    #[case::found(StepTarget::Found, MissingTargetPolicy::End, true)]
+    #[case::waiting(StepTarget::Waiting, MissingTargetPolicy::Floating, false)]
+    #[case::missing_floating(StepTarget::Missing, MissingTargetPolicy::Floating, true)]
+    #[case::missing_skip(StepTarget::Missing, MissingTargetPolicy::Skip, false)]
+    #[case::missing_end(StepTarget::Missing, MissingTargetPolicy::End, false)]
+    fn test_step_target_is_shown(
+        #[case] target: StepTarget,
+        #[case] policy: MissingTargetPolicy,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(target.is_shown(policy), expected);
+    }
+
+    #[rstest]
+    #[case::start_active(TourState::Active(2), TourAction::Start, TourState::Active(2))]
+    #[case::start_skipped(TourState::Skipped, TourAction::Start, TourState::Active(0))]
+    #[case::restart_active(TourState::Active(2), TourAction::Restart, TourState::Active(0))]
+    #[case::stop_active(TourState::Active(2), TourAction::Stop, TourState::NotStarted)]
+    #[case::skip_active(TourState::Active(1), TourAction::Skip, TourState::Skipped)]
+    #[case::skip_not_started(TourState::NotStarted, TourAction::Skip, TourState::NotStarted)]
+    #[case::complete_active(TourState::Active(1), TourAction::Complete, TourState::Completed)]
+    #[case::complete_skipped(TourState::Skipped, TourAction::Complete, TourState::Skipped)]
+    #[case::next_not_started(TourState::NotStarted, TourAction::Next, TourState::NotStarted)]
+    fn test_reduce(
+        #[case] state: TourState,
+        #[case] action: TourAction,
+        #[case] expected: TourState,
+    ) {
+        let controller = Rc::new(TourController {
+            state,
+            step_count: 3,
+            ..Default::default()
+        });
+        assert_eq!(controller.reduce(action).state(), expected);
+    }
+}
@@ -47,18 +47,22 @@
 //!         id: Some("main-tour".to_string()),
 //!         steps: vec![
 //!             TourStep {
-//!                 selector: ".intro-element".to_string(),
+//!                 selector: Some(".intro-element".to_string()),
 //!                 content: "Welcome to our app! This is the first step of the tour.".to_string(),
+//!                 ..Default::default()
 //!             },
 //!             TourStep {
-//!                 selector: "#feature-button".to_string(),
+//!                 selector: Some("#feature-button".to_string()),
 //!                 content: "Click here to see our main feature.".to_string(),
+//!                 ..Default::default()
 //!             },
 //!             TourStep {
-//!                 selector: ".help-section".to_string(),
+//!                 selector: Some(".help-section".to_string()),
 //!                 content: "If you need help, you can always find it here.".to_string(),
+//!                 ..Default::default()
 //!             },
 //!         ],
+//!         ..Default::default()
 //!     };
 //!
 //!     html! {
@@ -78,14 +82,24 @@
 //! - `config`: Defines the `TourConfig` struct for configuring tours.
 //! - `step`: Contains the `TourStep` struct for individual tour steps.
 //! - `tour`: Implements the main `Tour` component.
+//! - `controller`: Contains the headless `TourController` state machine driving a tour.
+//! - `provider`: Implements the `TourProvider` context and the `use_tour` hook for programmatic control.
+//! - `registry`: Contains the `TourRegistry` running one named tour at a time.
+//! - `storage`: Defines the `TourStorage` trait and its backends for persisting tour state.
+//! - `clock`: Defines the `Clock` trait used as time source for display frequency policies.
 //! - Other modules provide supporting functionality and components.
 //!
 //! # License
 //!
 //! This project is licensed under the MIT License. See the [LICENSE](https://github.com/chriamue/yew-tou-rs/blob/main/LICENSE) file for details.
 
+mod clock;
 mod components;
+mod controller;
 mod models;
+mod provider;
+mod registry;
+mod storage;
 mod tour;
 mod utils;
 
@@ -104,7 +118,21 @@ mod utils;
 ///
 /// For a complete usage example, see the crate-level documentation.
 pub mod prelude {
+    pub use crate::clock::{Clock, ClockHandle, ManualClock, SystemClock};
+    pub use crate::controller::{TourAction, TourController, TourState};
     pub use crate::models::TourConfig;
     pub use crate::models::TourStep;
+    pub use crate::models::{
+        Alignment, DisplayFrequency, Middleware, MissingTargetPolicy, Placement, ReshowPolicy,
+        StepTransition, TourError, TourEvent, TourRecord, TourStatus,
+    };
+    pub use crate::provider::{TourContext, TourProvider, UseTourHandle, use_tour};
+    pub use crate::registry::{TourRegistry, TourRegistryAction};
+    pub use crate::storage::{
+        ConsentStorage, MemoryTourStorage, NamespacedStorage, TourStorage, TourStorageHandle,
+        encode_cookie_component, parse_cookies, reset_all_tours, reset_tour,
+    };
+    #[cfg(feature = "storage")]
+    pub use crate::storage::{CookieTourStorage, LocalTourStorage, SessionTourStorage};
     pub use crate::tour::Tour;
 }
@@ -1,4 +1,8 @@
-use crate::models::TourStep;
+use crate::clock::ClockHandle;
+use crate::models::{
+    DisplayFrequency, Middleware, MissingTargetPolicy, ReshowPolicy, TourError, TourEvent, TourStep,
+};
+use crate::storage::TourStorageHandle;
 use serde::{Deserialize, Serialize};
 use yew::{Callback, Properties};
 
@@ -11,6 +15,13 @@ use yew::{Callback, Properties};
 ///
 /// * `id` - An optional unique identifier for the tour.
 /// * `steps` - A vector of `TourStep`s that define the content and order of the tour.
+/// * `auto_start` - Whether the tour starts as soon as it is mounted.
+/// * `missing_target` - What to do when the target element of a step can not be found.
+/// * `max_width`, `max_height` - The maximum width of the tooltip and height of its content.
+/// * `middleware` - The pipeline placing the tooltip next to its target.
+/// * `resume` - Whether an interrupted tour resumes at the last viewed step.
+/// * `version`, `reshow` - Show a completed or dismissed tour again after it changed.
+/// * `frequency` - How often the tour is shown.
 ///
 /// # Examples
 ///
@@ -21,14 +32,17 @@ use yew::{Callback, Properties};
 ///     id: Some("main-tour".to_string()),
 ///     steps: vec![
 ///         TourStep {
-///             selector: ".intro-element".to_string(),
+///             selector: Some(".intro-element".to_string()),
 ///             content: "Welcome to the tour!".to_string(),
+///             ..Default::default()
 ///         },
 ///         TourStep {
-///             selector: "#feature-button".to_string(),
+///             selector: Some("#feature-button".to_string()),
 ///             content: "Click here to see our main feature.".to_string(),
+///             ..Default::default()
 ///         },
 ///     ],
+///     ..Default::default()
 /// };
 /// ```
 ///
@@ -46,12 +60,28 @@ use yew::{Callback, Properties};
 /// This struct can be cloned and easily printed for debugging purposes.
 #[derive(Properties, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TourConfig {
-    /// Optional callback invoked when the tour is closed or completed.
+    /// Optional callback invoked with every `TourEvent` of the tour, e.g. when it is
+    /// started, a step is shown, or the tour is skipped or completed.
     ///
     /// This will not be serialized/deserialized.
     #[serde(skip)]
     #[prop_or_default]
-    pub on_close: Option<Callback<()>>,
+    pub on_event: Option<Callback<TourEvent>>,
+
+    /// Optional callback invoked when the user went through all steps of the tour.
+    ///
+    /// This will not be serialized/deserialized.
+    #[serde(skip)]
+    #[prop_or_default]
+    pub on_complete: Option<Callback<()>>,
+
+    /// Optional callback invoked with the index of the current step when the user
+    /// closes the tour before reaching the end.
+    ///
+    /// This will not be serialized/deserialized.
+    #[serde(skip)]
+    #[prop_or_default]
+    pub on_dismiss: Option<Callback<usize>>,
 
     /// An optional unique identifier for the tour.
     ///
@@ -68,4 +98,140 @@ pub struct TourConfig {
     /// Each step is represented by a `TourStep` struct, which includes information
     /// about what element to highlight and what content to display for that step.
     pub steps: Vec<TourStep>,
+
+    /// Whether the tour starts as soon as it is mounted.
+    ///
+    /// Set this to `false` to start the tour later through the `use_tour` hook
+    /// of a surrounding `TourProvider`. Defaults to `true`.
+    #[prop_or(true)]
+    #[serde(default = "default_auto_start")]
+    pub auto_start: bool,
+
+    /// Whether an interrupted tour, e.g. by reloading the page, resumes at the last
+    /// viewed step instead of restarting at the first step.
+    ///
+    /// The last viewed step is persisted in the tour's storage, by its `id` if it has
+    /// one and by its index otherwise. Resuming a tour does not count as showing it
+    /// again for its `frequency`. Defaults to `false`.
+    #[prop_or_default]
+    #[serde(default)]
+    pub resume: bool,
+
+    /// The version of the tour's content.
+    ///
+    /// The version is stored when the tour is completed or dismissed. Raise it when
+    /// the steps change to show the tour again to users who have seen an older
+    /// version. Defaults to `0`.
+    #[prop_or_default]
+    #[serde(default)]
+    pub version: u32,
+
+    /// How often the tour is shown.
+    ///
+    /// Defaults to showing the tour until it is completed or dismissed.
+    #[prop_or_default]
+    #[serde(default)]
+    pub frequency: DisplayFrequency,
+
+    /// Which steps are shown again after the `version` was raised.
+    ///
+    /// Defaults to showing the whole tour again.
+    #[prop_or_default]
+    #[serde(default)]
+    pub reshow: ReshowPolicy,
+
+    /// The maximum width of the tooltip in pixels. Steps can override it.
+    ///
+    /// The tooltip is measured after it rendered and placed with its real size.
+    /// Defaults to 300 pixels.
+    #[prop_or_default]
+    #[serde(default)]
+    pub max_width: Option<u32>,
+
+    /// The maximum height of the step content in pixels, scrolling longer content.
+    /// Steps can override it. Defaults to no limit.
+    #[prop_or_default]
+    #[serde(default)]
+    pub max_height: Option<u32>,
+
+    /// The pipeline placing the tooltip next to its target, e.g. how it avoids the
+    /// window edges.
+    ///
+    /// Defaults to `Middleware::defaults`.
+    #[prop_or_else(Middleware::defaults)]
+    #[serde(default = "Middleware::defaults")]
+    pub middleware: Vec<Middleware>,
+
+    /// What to do when the target element of a step can not be found.
+    ///
+    /// Steps can override this with their own `missing_target` policy.
+    /// Defaults to showing the step as a floating tooltip.
+    #[prop_or_default]
+    #[serde(default)]
+    pub missing_target: MissingTargetPolicy,
+
+    /// Optional callback invoked when the tour runs into an error, e.g. a missing target
+    /// element of a step, whatever its `MissingTargetPolicy`.
+    ///
+    /// This will not be serialized/deserialized.
+    #[serde(skip)]
+    #[prop_or_default]
+    pub on_error: Option<Callback<TourError>>,
+
+    /// Optional storage to persist the tour state in.
+    ///
+    /// If `None`, the storage of a surrounding `TourProvider` is used, and otherwise
+    /// `LocalTourStorage` with the `storage` feature. Without the feature, the tour
+    /// state is not persisted by default.
+    ///
+    /// This will not be serialized/deserialized.
+    #[serde(skip)]
+    #[prop_or_default]
+    pub storage: Option<TourStorageHandle>,
+
+    /// Optional namespace for the persisted keys of the tour, e.g. built from the id of
+    /// the signed in user, so tour state is not shared between accounts.
+    ///
+    /// The namespace is applied on top of the namespace of a surrounding `TourProvider`.
+    #[prop_or_default]
+    #[serde(default)]
+    pub namespace: Option<String>,
+
+    /// Optional clock used as time source of the `frequency` policy.
+    ///
+    /// If `None`, the `SystemClock` is used.
+    ///
+    /// This will not be serialized/deserialized.
+    #[serde(skip)]
+    #[prop_or_default]
+    pub clock: Option<ClockHandle>,
+}
+
+fn default_auto_start() -> bool {
+    true
+}
+
+impl Default for TourConfig {
+    fn default() -> Self {
+        Self {
+            on_event: None,
+            on_complete: None,
+            on_dismiss: None,
+            id: None,
+            steps: Vec::new(),
+            auto_start: default_auto_start(),
+            resume: false,
+            version: 0,
+            frequency: DisplayFrequency::default(),
+            reshow: ReshowPolicy::default(),
+            max_width: None,
+            max_height: None,
+            middleware: Middleware::defaults(),
+            missing_target: MissingTargetPolicy::default(),
+            on_error: None,
+            storage: None,
+            namespace: None,
+            clock: None,
+        }
+    }
 }
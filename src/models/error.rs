@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// Errors reported by a tour through `TourConfig.on_error`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TourError {
+    /// The target element of a step could not be found.
+    TargetNotFound {
+        /// The index of the step.
+        step: usize,
+        /// The CSS selector which did not match any element.
+        selector: String,
+    },
+}
+
+impl fmt::Display for TourError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TourError::TargetNotFound { step, selector } => write!(
+                f,
+                "Target element '{}' of step {} not found",
+                selector, step
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TourError {}
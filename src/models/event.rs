@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+/// Events emitted by a tour through `TourConfig.on_event`, e.g. to feed analytics.
+///
+/// Step indices refer to `TourConfig.steps`, also when only some of the steps are shown
+/// again because of `ReshowPolicy::NewSteps`.
+///
+/// # Serialization
+///
+/// Events serialize to JSON objects tagged with the event name, for example
+/// `{"event":"skipped","at":2}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TourEvent {
+    /// The tour was started.
+    Started,
+
+    /// A step was shown to the user.
+    ///
+    /// Steps waiting for their target and steps left because their target is missing
+    /// are not reported.
+    StepShown {
+        /// The index of the step.
+        index: usize,
+        /// The `id` of the step, if it has one.
+        id: Option<String>,
+    },
+
+    /// The tour moved on to the next step.
+    Next { from: usize, to: usize },
+
+    /// The tour moved back to the previous step.
+    Prev { from: usize, to: usize },
+
+    /// The tour jumped to a step which is not adjacent, or was sent to a step programmatically.
+    Jumped { from: usize, to: usize },
+
+    /// The user closed the tour before reaching the end.
+    Skipped {
+        /// The index of the step the tour was closed on.
+        at: usize,
+    },
+
+    /// The user went through all steps of the tour.
+    Completed,
+
+    /// The target element of a step could not be found.
+    TargetMissing {
+        /// The index of the step.
+        index: usize,
+        /// The CSS selector which did not match any element.
+        selector: String,
+    },
+}
+
+impl TourEvent {
+    /// Returns the event with its step indices mapped by `f`.
+    pub(crate) fn map_steps(self, f: impl Fn(usize) -> usize) -> Self {
+        match self {
+            TourEvent::StepShown { index, id } => TourEvent::StepShown {
+                index: f(index),
+                id,
+            },
+            TourEvent::Next { from, to } => TourEvent::Next {
+                from: f(from),
+                to: f(to),
+            },
+            TourEvent::Prev { from, to } => TourEvent::Prev {
+                from: f(from),
+                to: f(to),
+            },
+            TourEvent::Jumped { from, to } => TourEvent::Jumped {
+                from: f(from),
+                to: f(to),
+            },
+            TourEvent::Skipped { at } => TourEvent::Skipped { at: f(at) },
+            TourEvent::TargetMissing { index, selector } => TourEvent::TargetMissing {
+                index: f(index),
+                selector,
+            },
+            TourEvent::Started | TourEvent::Completed => self,
+        }
+    }
+}
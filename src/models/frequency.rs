@@ -0,0 +1,150 @@
+use crate::models::{TourRecord, TourStatus};
+use serde::{Deserialize, Serialize};
+
+/// How often a tour is shown to a user.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{DisplayFrequency, TourConfig};
+///
+/// // Show the tour again a week after it was completed or dismissed
+/// let config = TourConfig {
+///     id: Some("tips".to_string()),
+///     frequency: DisplayFrequency::Cooldown(7 * 24 * 60 * 60 * 1000),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayFrequency {
+    /// Shows the tour until it is completed or dismissed.
+    #[default]
+    UntilFinished,
+
+    /// Shows the tour only once, even if it was not finished.
+    Once,
+
+    /// Shows the tour the given number of times, unless it is finished before.
+    Times(u32),
+
+    /// Shows the tour until it is completed. Dismissing it does not count.
+    UntilCompleted,
+
+    /// Shows the tour again once the given milliseconds passed since it was last
+    /// completed or dismissed.
+    Cooldown(u64),
+}
+
+impl DisplayFrequency {
+    /// Returns `true` if a tour with the given record should be shown at `now`,
+    /// in milliseconds since the Unix epoch.
+    ///
+    /// Tours which are resumed with `TourConfig.resume` are shown whatever the frequency,
+    /// resuming them does not count as showing them again.
+    pub fn should_show(&self, record: &TourRecord, now: u64) -> bool {
+        match self {
+            DisplayFrequency::UntilFinished => !record.is_finished(),
+            DisplayFrequency::Once => record.times_shown == 0,
+            DisplayFrequency::Times(times) => !record.is_finished() && record.times_shown < *times,
+            DisplayFrequency::UntilCompleted => record.status != TourStatus::Completed,
+            DisplayFrequency::Cooldown(cooldown) => {
+                !record.is_finished()
+                    || record
+                        .last_seen
+                        .is_none_or(|finished| now >= finished.saturating_add(*cooldown))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TourStep;
+    use rstest::rstest;
+
+    fn record(status: TourStatus, times_shown: u32) -> TourRecord {
+        TourRecord {
+            status,
+            times_shown,
+            last_seen: (times_shown > 0).then_some(1_000),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case::until_finished_new(DisplayFrequency::UntilFinished, record(TourStatus::New, 0), true)]
+    #[case::until_finished_shown(
+        DisplayFrequency::UntilFinished,
+        record(TourStatus::InProgress, 1),
+        true
+    )]
+    #[case::until_finished_dismissed(
+        DisplayFrequency::UntilFinished,
+        record(TourStatus::Dismissed, 1),
+        false
+    )]
+    #[case::once_new(DisplayFrequency::Once, record(TourStatus::New, 0), true)]
+    #[case::once_shown(DisplayFrequency::Once, record(TourStatus::InProgress, 1), false)]
+    #[case::times_left(DisplayFrequency::Times(2), record(TourStatus::InProgress, 1), true)]
+    #[case::times_used(DisplayFrequency::Times(1), record(TourStatus::InProgress, 1), false)]
+    #[case::times_dismissed(DisplayFrequency::Times(2), record(TourStatus::Dismissed, 1), false)]
+    #[case::until_completed_dismissed(
+        DisplayFrequency::UntilCompleted,
+        record(TourStatus::Dismissed, 1),
+        true
+    )]
+    #[case::until_completed_completed(
+        DisplayFrequency::UntilCompleted,
+        record(TourStatus::Completed, 1),
+        false
+    )]
+    #[case::cooldown_new(DisplayFrequency::Cooldown(500), record(TourStatus::New, 0), true)]
+    fn test_should_show(
+        #[case] frequency: DisplayFrequency,
+        #[case] record: TourRecord,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(frequency.should_show(&record, 1_000), expected);
+    }
+
+    #[rstest]
+    #[case::once(DisplayFrequency::Once, false)]
+    #[case::times(DisplayFrequency::Times(2), true)]
+    fn test_resume_is_not_shown_again(#[case] frequency: DisplayFrequency, #[case] expected: bool) {
+        let steps = vec![TourStep::floating("first"), TourStep::floating("second")];
+        let mut record = TourRecord::default();
+        record.start(1_000);
+        record.show_step(1, None, 1_100);
+
+        // The tour is interrupted by reloads and resumed each time, whatever the frequency
+        for now in [2_000, 3_000] {
+            assert_eq!(record.resume_step_in(&steps), Some(1));
+            record.resume(now);
+        }
+
+        // Resuming did not use up any of the shows
+        assert_eq!(record.times_shown, 1);
+        assert_eq!(record.last_seen, Some(3_000));
+        assert_eq!(frequency.should_show(&record, 4_000), expected);
+    }
+
+    #[rstest]
+    #[case::running(1_499, false)]
+    #[case::passed(1_500, true)]
+    #[case::long_passed(10_000, true)]
+    fn test_cooldown(#[case] now: u64, #[case] expected: bool) {
+        let frequency = DisplayFrequency::Cooldown(500);
+        assert_eq!(
+            frequency.should_show(&record(TourStatus::Completed, 1), now),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_cooldown_does_not_overflow() {
+        let frequency = DisplayFrequency::Cooldown(u64::MAX);
+        assert!(!frequency.should_show(&record(TourStatus::Dismissed, 1), u64::MAX - 1));
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// The size of the tooltip's arrow, which the default pipeline keeps the tooltip away by.
+pub(crate) const ARROW_SIZE: i32 = 10;
+
+/// A step of the pipeline placing the tooltip next to its target, in the spirit of
+/// floating-ui's middleware.
+///
+/// The steps run in the order they are given, each one adjusting the position the
+/// previous steps calculated. A good order is offset, flip, size, shift and arrow.
+/// The pipeline of a tour is set in `TourConfig.middleware`.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{Middleware, TourConfig};
+///
+/// // Keep a bigger distance to the target and to the window edges
+/// let config = TourConfig {
+///     middleware: vec![
+///         Middleware::Offset(16),
+///         Middleware::Flip,
+///         Middleware::Shift { padding: 8 },
+///         Middleware::Arrow { padding: 12 },
+///     ],
+///     ..Default::default()
+/// };
+/// ```
+///
+/// # Serialization
+///
+/// Middleware serializes like `{"offset":16}`, `"flip"` or `{"shift":{"padding":8}}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Middleware {
+    /// Moves the tooltip away from the target by the given pixels.
+    Offset(i32),
+
+    /// Moves the tooltip to the next side of `Placement::fallbacks` if it does not fit
+    /// on its side.
+    Flip,
+
+    /// Moves the tooltip to stay within the window, keeping `padding` pixels to its edges.
+    Shift { padding: i32 },
+
+    /// Limits the size of the tooltip to the space on its side, keeping `padding` pixels
+    /// to the window edges. Longer content scrolls.
+    Size { padding: i32 },
+
+    /// Aims the arrow at the center of the target, keeping `padding` pixels to the
+    /// corners of the tooltip.
+    Arrow { padding: i32 },
+}
+
+impl Middleware {
+    /// Returns the default pipeline: an offset for the arrow, flip, shift and arrow.
+    pub fn defaults() -> Vec<Middleware> {
+        vec![
+            Middleware::Offset(ARROW_SIZE),
+            Middleware::Flip,
+            Middleware::Shift { padding: 0 },
+            Middleware::Arrow {
+                padding: ARROW_SIZE,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&Middleware::defaults()).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"offset":10},"flip",{"shift":{"padding":0}},{"arrow":{"padding":10}}]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<Middleware>>(&json).unwrap(),
+            Middleware::defaults()
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// What a tour does when the target element of a step can not be found.
+///
+/// The missing target is reported to `TourConfig.on_error` whatever the policy,
+/// the policy only decides where the tour goes. The policy can be set for a whole
+/// tour in `TourConfig.missing_target` and overridden for single steps in
+/// `TourStep.missing_target`.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{MissingTargetPolicy, TourStep};
+///
+/// let step = TourStep {
+///     selector: Some("#optional-widget".to_string()),
+///     content: "This widget is not always there.".to_string(),
+///     missing_target: Some(MissingTargetPolicy::Skip),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingTargetPolicy {
+    /// Moves on to the next step, or to the previous one when going back.
+    Skip,
+
+    /// Shows the step as a floating tooltip in the center of the screen.
+    #[default]
+    Floating,
+
+    /// Ends the tour like `use_tour().stop()`. The user did not close it, so no
+    /// `TourEvent::Skipped` is emitted, `on_dismiss` is not called and the tour is
+    /// not recorded as dismissed.
+    End,
+}
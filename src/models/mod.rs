@@ -1,7 +1,26 @@
 mod config;
+mod error;
+mod event;
+mod frequency;
+mod middleware;
+mod missing_target;
+mod placement;
+mod record;
 mod rect;
+mod reshow;
 mod step;
+mod transition;
 
 pub use config::TourConfig;
+pub use error::TourError;
+pub use event::TourEvent;
+pub use frequency::DisplayFrequency;
+pub(crate) use middleware::ARROW_SIZE;
+pub use middleware::Middleware;
+pub use missing_target::MissingTargetPolicy;
+pub use placement::{Alignment, Placement};
+pub use record::{TourRecord, TourStatus};
 pub use rect::*;
+pub use reshow::ReshowPolicy;
 pub use step::TourStep;
+pub use transition::StepTransition;
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+/// The side of the target element a tooltip is placed on.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{Placement, TourStep};
+///
+/// let step = TourStep {
+///     placement: Placement::Right,
+///     ..TourStep::new("#sidebar", "Find all your projects here.")
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Placement {
+    /// Places the tooltip on the side with the most space.
+    #[default]
+    Auto,
+
+    /// Places the tooltip above the target.
+    Top,
+
+    /// Places the tooltip below the target.
+    Bottom,
+
+    /// Places the tooltip left of the target.
+    Left,
+
+    /// Places the tooltip right of the target.
+    Right,
+}
+
+impl Placement {
+    /// Returns the opposite side, `Auto` for `Auto`.
+    pub fn opposite(&self) -> Self {
+        match self {
+            Placement::Auto => Placement::Auto,
+            Placement::Top => Placement::Bottom,
+            Placement::Bottom => Placement::Top,
+            Placement::Left => Placement::Right,
+            Placement::Right => Placement::Left,
+        }
+    }
+
+    /// Returns the sides to try in order when this side is preferred: the side itself,
+    /// the opposite side and then the perpendicular sides.
+    ///
+    /// `Auto` has no order, the side with the most space is used.
+    pub fn fallbacks(&self) -> Vec<Placement> {
+        match self {
+            Placement::Auto => Vec::new(),
+            Placement::Top | Placement::Bottom => {
+                vec![*self, self.opposite(), Placement::Right, Placement::Left]
+            }
+            Placement::Left | Placement::Right => {
+                vec![*self, self.opposite(), Placement::Bottom, Placement::Top]
+            }
+        }
+    }
+}
+
+/// How a tooltip is aligned along the edge of the target element it is placed on.
+///
+/// Along the top and bottom edges, `Start` aligns the left edges of tooltip and target
+/// and `End` the right edges. Along the left and right edges, `Start` aligns the top
+/// edges and `End` the bottom edges.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{Alignment, Placement, TourStep};
+///
+/// // Below the left end of a wide toolbar
+/// let step = TourStep {
+///     placement: Placement::Bottom,
+///     alignment: Alignment::Start,
+///     ..TourStep::new("#toolbar", "All your tools in one place.")
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Alignment {
+    /// Aligns the tooltip with the start of the edge, the left or the top.
+    Start,
+
+    /// Centers the tooltip on the edge.
+    #[default]
+    Center,
+
+    /// Aligns the tooltip with the end of the edge, the right or the bottom.
+    End,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::top(Placement::Top, vec![Placement::Top, Placement::Bottom, Placement::Right, Placement::Left])]
+    #[case::left(Placement::Left, vec![Placement::Left, Placement::Right, Placement::Bottom, Placement::Top])]
+    #[case::auto(Placement::Auto, vec![])]
+    fn test_fallbacks(#[case] placement: Placement, #[case] expected: Vec<Placement>) {
+        assert_eq!(placement.fallbacks(), expected);
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(
+            serde_json::to_string(&Placement::Bottom).unwrap(),
+            r#""bottom""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Placement>(r#""auto""#).unwrap(),
+            Placement::Auto
+        );
+    }
+}
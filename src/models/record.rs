@@ -0,0 +1,247 @@
+use crate::models::TourStep;
+use crate::storage::{TourStorageHandle, add_persisted_tour};
+use serde::{Deserialize, Serialize};
+
+/// The status of a tour persisted in its `TourRecord`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TourStatus {
+    /// The tour was never shown.
+    #[default]
+    New,
+
+    /// The tour was started and not finished yet.
+    InProgress,
+
+    /// The user went through all steps of the tour.
+    Completed,
+
+    /// The user closed the tour before reaching the end.
+    Dismissed,
+}
+
+/// What is persisted about a tour, stored as JSON under the key `{id}-record`.
+///
+/// Timestamps are milliseconds since the Unix epoch.
+///
+/// # Serialization
+///
+/// Records serialize to JSON objects, for example
+/// `{"status":"completed","last_step":null,"last_step_id":null,"times_shown":1,"first_seen":1700000000000,"last_seen":1700000060000,"version":2}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TourRecord {
+    /// The status of the tour.
+    pub status: TourStatus,
+
+    /// The index in `TourConfig.steps` of the last viewed step of a tour in progress.
+    pub last_step: Option<usize>,
+
+    /// The `id` of the last viewed step of a tour in progress, if it has one.
+    pub last_step_id: Option<String>,
+
+    /// How often the tour was shown.
+    pub times_shown: u32,
+
+    /// When the tour was shown for the first time.
+    pub first_seen: Option<u64>,
+
+    /// When the tour was last shown, completed or dismissed.
+    pub last_seen: Option<u64>,
+
+    /// The `TourConfig.version` of the tour when it was last completed or dismissed.
+    pub version: u32,
+}
+
+impl TourRecord {
+    /// Returns the storage key of the record of the tour with the given id.
+    pub fn key(id: &str) -> String {
+        format!("{}-record", id)
+    }
+
+    /// Loads the record of a tour, or the state persisted by earlier versions.
+    ///
+    /// Earlier versions stored `{id}-show=false` for finished tours and did not tell
+    /// completed and dismissed tours apart. Loading never writes to the storage, the
+    /// old key is replaced once the record is saved.
+    pub fn load(storage: &TourStorageHandle, id: &str) -> Self {
+        if let Some(record) = storage.get_value(&Self::key(id)) {
+            return record;
+        }
+        match storage.get_value::<bool>(&Self::legacy_key(id)) {
+            Some(false) => Self {
+                status: TourStatus::Dismissed,
+                ..Self::default()
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Stores the record of the tour with the given id, replacing the key of earlier versions.
+    ///
+    /// The id is also added to the tours persisted in the storage, which are reset by
+    /// `reset_all_tours`.
+    pub fn save(&self, storage: &TourStorageHandle, id: &str) -> Result<(), String> {
+        storage.set_value(&Self::key(id), self)?;
+        storage.remove(&Self::legacy_key(id));
+        add_persisted_tour(storage, id)
+    }
+
+    /// Returns the key earlier versions stored whether to show the tour in.
+    pub(crate) fn legacy_key(id: &str) -> String {
+        format!("{}-show", id)
+    }
+
+    /// Returns the index of the last viewed step in the given steps, found by its id or index.
+    pub fn last_step_in(&self, steps: &[TourStep]) -> Option<usize> {
+        self.last_step_id
+            .as_ref()
+            .and_then(|step_id| {
+                steps
+                    .iter()
+                    .position(|step| step.id.as_ref() == Some(step_id))
+            })
+            .or(self.last_step)
+    }
+
+    /// Returns the index of the step an interrupted tour can be resumed at in the given steps.
+    pub fn resume_step_in(&self, steps: &[TourStep]) -> Option<usize> {
+        (self.status == TourStatus::InProgress)
+            .then(|| self.last_step_in(steps))
+            .flatten()
+    }
+
+    /// Returns `true` if the tour was completed or dismissed.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, TourStatus::Completed | TourStatus::Dismissed)
+    }
+
+    /// Records that the tour was started at `now`.
+    pub fn start(&mut self, now: u64) {
+        self.status = TourStatus::InProgress;
+        self.last_step = None;
+        self.last_step_id = None;
+        self.times_shown = self.times_shown.saturating_add(1);
+        self.first_seen.get_or_insert(now);
+        self.last_seen = Some(now);
+    }
+
+    /// Records that an interrupted tour was resumed at `now`. Unlike `start`, this does
+    /// not count as showing the tour again.
+    pub fn resume(&mut self, now: u64) {
+        self.status = TourStatus::InProgress;
+        self.last_seen = Some(now);
+    }
+
+    /// Records that a step was shown at `now`.
+    pub fn show_step(&mut self, index: usize, id: Option<String>, now: u64) {
+        self.last_step = Some(index);
+        self.last_step_id = id;
+        self.last_seen = Some(now);
+    }
+
+    /// Records that the given version of the tour was completed or dismissed at `now`.
+    pub fn finish(&mut self, status: TourStatus, version: u32, now: u64) {
+        self.status = status;
+        self.last_step = None;
+        self.last_step_id = None;
+        self.last_seen = Some(now);
+        self.version = version;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryTourStorage;
+    use rstest::rstest;
+
+    fn storage() -> TourStorageHandle {
+        TourStorageHandle::new(MemoryTourStorage::default())
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let mut record = TourRecord::default();
+        record.start(10);
+        record.show_step(1, Some("editor".to_string()), 20);
+        assert_eq!(record.status, TourStatus::InProgress);
+        assert_eq!(record.last_step, Some(1));
+        assert_eq!(record.last_step_id.as_deref(), Some("editor"));
+        assert!(!record.is_finished());
+
+        record.finish(TourStatus::Completed, 2, 30);
+        assert!(record.is_finished());
+        assert_eq!(record.last_step, None);
+        assert_eq!(record.version, 2);
+
+        record.start(40);
+        assert_eq!(record.times_shown, 2);
+        assert_eq!(record.first_seen, Some(10));
+        assert_eq!(record.last_seen, Some(40));
+    }
+
+    #[rstest]
+    #[case::by_id(Some("second"), Some(0), Some(1))]
+    #[case::unknown_id(Some("removed"), Some(0), Some(0))]
+    #[case::by_index(None, Some(2), Some(2))]
+    #[case::none(None, None, None)]
+    fn test_last_step_in(
+        #[case] last_step_id: Option<&str>,
+        #[case] last_step: Option<usize>,
+        #[case] expected: Option<usize>,
+    ) {
+        let steps: Vec<TourStep> = ["first", "second"]
+            .into_iter()
+            .map(|id| TourStep {
+                id: Some(id.to_string()),
+                ..TourStep::floating(id)
+            })
+            .collect();
+        let record = TourRecord {
+            last_step_id: last_step_id.map(str::to_string),
+            last_step,
+            ..Default::default()
+        };
+        assert_eq!(record.last_step_in(&steps), expected);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let storage = storage();
+        let mut record = TourRecord::default();
+        record.start(10);
+        record.save(&storage, "tour").unwrap();
+        assert_eq!(TourRecord::load(&storage, "tour"), record);
+        assert_eq!(TourRecord::load(&storage, "other"), TourRecord::default());
+    }
+
+    #[test]
+    fn test_load_fills_missing_fields() {
+        let storage = storage();
+        storage
+            .set("tour-record", r#"{"status":"dismissed"}"#)
+            .unwrap();
+        let record = TourRecord::load(&storage, "tour");
+        assert_eq!(record.status, TourStatus::Dismissed);
+        assert_eq!(record.times_shown, 0);
+    }
+
+    #[rstest]
+    #[case::finished("false", TourStatus::Dismissed)]
+    #[case::not_finished("true", TourStatus::New)]
+    fn test_legacy_key(#[case] show: &str, #[case] expected: TourStatus) {
+        let storage = storage();
+        storage.set("tour-show", show).unwrap();
+        let mut record = TourRecord::load(&storage, "tour");
+        assert_eq!(record.status, expected);
+        // Loading does not write, saving replaces the old key
+        assert_eq!(storage.get("tour-show").as_deref(), Some(show));
+        assert_eq!(storage.get("tour-record"), None);
+
+        record.start(10);
+        record.save(&storage, "tour").unwrap();
+        assert_eq!(storage.get("tour-show"), None);
+        assert_eq!(TourRecord::load(&storage, "tour"), record);
+    }
+}
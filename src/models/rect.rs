@@ -1,4 +1,8 @@
-use web_sys::Element;
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
+use web_sys::{Element, MutationObserver, MutationObserverInit};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Rect {
@@ -16,11 +20,6 @@ pub struct Rect {
 }
 
 impl Rect {
-    /// Calculates the area of the rectangle.
-    pub fn area(&self) -> i32 {
-        self.width * self.height
-    }
-
     /// Returns the leftmost X coordinate of the rectangle.
     pub fn left(&self) -> i32 {
         self.x
@@ -40,13 +39,6 @@ impl Rect {
     pub fn bottom(&self) -> i32 {
         self.y + self.height
     }
-
-    /// Calculates the overlap area between this rectangle and another rectangle.
-    pub fn overlap(&self, other: &Rect) -> i32 {
-        let x_overlap = (self.right().min(other.right()) - self.left().max(other.left())).max(0);
-        let y_overlap = (self.bottom().min(other.bottom()) - self.top().max(other.top())).max(0);
-        x_overlap * y_overlap
-    }
 }
 
 /// Rect from Tuple
@@ -87,6 +79,117 @@ pub fn get_element(selector: &str) -> Result<Element, String> {
         .and_then(|element| element.ok_or_else(|| "Element not found".to_string()))
 }
 
+/// Waits until an element matching the selector is added to the document.
+///
+/// A `MutationObserver` watches the document for changes and `callback` is called
+/// once with the element as soon as the selector matches, or with an error when
+/// `timeout` milliseconds passed without a match. Dropping the returned
+/// `ElementWaiter` stops waiting without calling `callback`.
+pub fn wait_for_element(
+    selector: &str,
+    timeout: u32,
+    callback: impl Fn(Result<Element, String>) + 'static,
+) -> Result<ElementWaiter, String> {
+    let window = web_sys::window().ok_or_else(|| "Failed to get window".to_string())?;
+    let root = window
+        .document()
+        .and_then(|document| document.document_element())
+        .ok_or_else(|| "Failed to get document".to_string())?;
+
+    let done = Rc::new(Cell::new(false));
+    let callback = Rc::new(callback);
+
+    let on_mutation = {
+        let selector = selector.to_string();
+        let done = done.clone();
+        let callback = callback.clone();
+        Closure::<dyn FnMut(wasm_bindgen::JsValue, MutationObserver)>::new(
+            move |_, observer: MutationObserver| {
+                if done.get() {
+                    return;
+                }
+                if let Ok(element) = get_element(&selector) {
+                    done.set(true);
+                    observer.disconnect();
+                    callback(Ok(element));
+                }
+            },
+        )
+    };
+    let observer = MutationObserver::new(on_mutation.as_ref().unchecked_ref()).map_err(|e| {
+        e.as_string()
+            .unwrap_or_else(|| "Failed to create MutationObserver".to_string())
+    })?;
+    let options = MutationObserverInit::new();
+    options.set_child_list(true);
+    options.set_subtree(true);
+    options.set_attributes(true);
+    observer
+        .observe_with_options(&root, &options)
+        .map_err(|e| {
+            e.as_string()
+                .unwrap_or_else(|| "Failed to observe document".to_string())
+        })?;
+
+    let on_timeout = {
+        let selector = selector.to_string();
+        let done = done.clone();
+        let observer = observer.clone();
+        let callback = callback.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            if done.get() {
+                return;
+            }
+            done.set(true);
+            observer.disconnect();
+            callback(Err(format!("Timed out waiting for element '{}'", selector)));
+        })
+    };
+    let timeout_handle = window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            on_timeout.as_ref().unchecked_ref(),
+            timeout as i32,
+        )
+        .map_err(|e| {
+            e.as_string()
+                .unwrap_or_else(|| "Failed to set timeout".to_string())
+        })?;
+
+    // The element might have been added before the observer was set up
+    if let Ok(element) = get_element(selector) {
+        done.set(true);
+        observer.disconnect();
+        window.clear_timeout_with_handle(timeout_handle);
+        callback(Ok(element));
+    }
+
+    Ok(ElementWaiter {
+        observer,
+        timeout_handle,
+        _on_mutation: on_mutation,
+        _on_timeout: on_timeout,
+    })
+}
+
+/// Handle of an element lookup started with `wait_for_element`.
+///
+/// Waiting stops when the handle is dropped.
+pub struct ElementWaiter {
+    observer: MutationObserver,
+    timeout_handle: i32,
+    _on_mutation: Closure<dyn FnMut(wasm_bindgen::JsValue, MutationObserver)>,
+    _on_timeout: Closure<dyn FnMut()>,
+}
+
+impl Drop for ElementWaiter {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+        if let Some(window) = web_sys::window() {
+            window.clear_timeout_with_handle(self.timeout_handle);
+        }
+    }
+}
+
 pub fn get_scroll_offsets() -> Result<(f64, f64), String> {
     let window = web_sys::window().ok_or_else(|| "Failed to get window".to_string())?;
     let scroll_x = window.scroll_x().map_err(|e| {
@@ -116,16 +219,6 @@ pub fn get_element_rect(selector: &str) -> Result<Rect, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rstest::rstest;
-
-    #[rstest]
-    #[case::rect1((10, 20, 30, 40), 1200)]
-    #[case::rect2((0, 0, 5, 5), 25)]
-    #[case::rect3((15, 25, 0, 0), 0)]
-    fn test_area(#[case] rect: (i32, i32, i32, i32), #[case] expected_area: i32) {
-        let rect = Rect::from(rect);
-        assert_eq!(rect.area(), expected_area);
-    }
 
     #[test]
     fn test_left_right_top_bottom() {
@@ -140,55 +233,4 @@ mod tests {
         assert_eq!(rect.top(), 10);
         assert_eq!(rect.bottom(), 40);
     }
-
-    #[test]
-    fn test_overlap_no_overlap() {
-        let rect1 = Rect {
-            x: 10,
-            y: 10,
-            width: 10,
-            height: 10,
-        };
-        let rect2 = Rect {
-            x: 25,
-            y: 25,
-            width: 5,
-            height: 5,
-        };
-        assert_eq!(rect1.overlap(&rect2), 0);
-    }
-
-    #[test]
-    fn test_overlap_full_overlap() {
-        let rect1 = Rect {
-            x: 10,
-            y: 10,
-            width: 20,
-            height: 30,
-        };
-        let rect2 = Rect {
-            x: 5,
-            y: 5,
-            width: 30,
-            height: 40,
-        };
-        assert_eq!(rect1.overlap(&rect2), rect1.area());
-    }
-
-    #[test]
-    fn test_overlap_partial_overlap() {
-        let rect1 = Rect {
-            x: 10,
-            y: 10,
-            width: 20,
-            height: 30,
-        };
-        let rect2 = Rect {
-            x: 15,
-            y: 5,
-            width: 10,
-            height: 20,
-        };
-        assert_eq!(rect1.overlap(&rect2), 150);
-    }
 }
@@ -0,0 +1,129 @@
+use crate::models::TourStep;
+use serde::{Deserialize, Serialize};
+
+/// Which steps a tour shows again after its `TourConfig.version` was raised.
+///
+/// A completed or dismissed tour is shown again when the version seen by the user is
+/// lower than the version of the tour.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{ReshowPolicy, TourConfig, TourStep};
+///
+/// let config = TourConfig {
+///     id: Some("main-tour".to_string()),
+///     version: 2,
+///     reshow: ReshowPolicy::NewSteps,
+///     steps: vec![
+///         TourStep::floating("Welcome to our app!"),
+///         TourStep {
+///             selector: Some("#export-button".to_string()),
+///             content: "New: export your data.".to_string(),
+///             since_version: Some(2),
+///             ..Default::default()
+///         },
+///     ],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReshowPolicy {
+    /// Shows the whole tour again.
+    #[default]
+    Full,
+
+    /// Shows only the steps added after the seen version, see `TourStep.since_version`.
+    NewSteps,
+}
+
+impl ReshowPolicy {
+    /// Returns the steps to show to a user who has seen the given version of the tour.
+    pub fn steps(&self, steps: &[TourStep], seen_version: u32) -> Vec<TourStep> {
+        self.step_indices(steps, seen_version)
+            .into_iter()
+            .map(|index| steps[index].clone())
+            .collect()
+    }
+
+    /// Returns the indices of the steps to show to a user who has seen the given version
+    /// of the tour, in the order they are shown.
+    pub fn step_indices(&self, steps: &[TourStep], seen_version: u32) -> Vec<usize> {
+        match self {
+            ReshowPolicy::Full => (0..steps.len()).collect(),
+            ReshowPolicy::NewSteps => steps
+                .iter()
+                .enumerate()
+                .filter(|(_, step)| step.since_version.is_some_and(|since| since > seen_version))
+                .map(|(index, _)| index)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::TourController;
+    use crate::models::TourEvent;
+    use rstest::rstest;
+
+    fn steps() -> Vec<TourStep> {
+        vec![
+            TourStep::floating("always there"),
+            TourStep {
+                since_version: Some(1),
+                ..TourStep::floating("version 1")
+            },
+            TourStep {
+                since_version: Some(2),
+                ..TourStep::floating("version 2")
+            },
+        ]
+    }
+
+    #[rstest]
+    #[case::full(ReshowPolicy::Full, 1, &["always there", "version 1", "version 2"])]
+    #[case::new_since_0(ReshowPolicy::NewSteps, 0, &["version 1", "version 2"])]
+    #[case::new_since_1(ReshowPolicy::NewSteps, 1, &["version 2"])]
+    #[case::nothing_new(ReshowPolicy::NewSteps, 2, &[])]
+    fn test_steps(
+        #[case] policy: ReshowPolicy,
+        #[case] seen_version: u32,
+        #[case] expected: &[&str],
+    ) {
+        let contents: Vec<String> = policy
+            .steps(&steps(), seen_version)
+            .into_iter()
+            .map(|step| step.content)
+            .collect();
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn test_events_refer_to_all_steps() {
+        let steps = steps();
+        let indices = ReshowPolicy::NewSteps.step_indices(&steps, 0);
+        assert_eq!(indices, vec![1, 2]);
+
+        let mut controller = TourController::new(indices.len());
+        controller.start();
+        controller.next();
+        controller.prev();
+        controller.skip();
+        let events: Vec<_> = controller
+            .events_since(0)
+            .map(|event| event.clone().map_steps(|index| indices[index]))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                TourEvent::Started,
+                TourEvent::Next { from: 1, to: 2 },
+                TourEvent::Prev { from: 2, to: 1 },
+                TourEvent::Skipped { at: 1 },
+            ]
+        );
+    }
+}
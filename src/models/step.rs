@@ -1,4 +1,6 @@
+use crate::models::{Alignment, MissingTargetPolicy, Placement, StepTransition};
 use serde::{Deserialize, Serialize};
+use yew::Callback;
 
 /// Represents a single step in a tour.
 ///
@@ -7,8 +9,16 @@ use serde::{Deserialize, Serialize};
 ///
 /// # Fields
 ///
-/// * `selector` - A CSS selector string used to identify the element to highlight.
+/// * `id` - An optional identifier of the step, reported in `TourEvent::StepShown`.
+/// * `selector` - An optional CSS selector string used to identify the element to highlight.
 /// * `content` - The text content to display for this step of the tour.
+/// * `placement` - The preferred side of the target to place the tooltip on.
+/// * `alignment` - How the tooltip is aligned along the edge of the target.
+/// * `max_width`, `max_height` - The maximum width of the tooltip and height of its content for this step.
+/// * `missing_target` - Overrides the tour's policy for a missing target element.
+/// * `wait_for_target` - How long to wait for a target element which is rendered later.
+/// * `since_version` - The version of the tour which added the step.
+/// * `on_enter`, `on_leave`, `before_change` - Lifecycle hooks of the step.
 ///
 /// # Examples
 ///
@@ -16,26 +26,47 @@ use serde::{Deserialize, Serialize};
 /// use yew_tou_rs::prelude::TourStep;
 ///
 /// let step = TourStep {
-///     selector: ".intro-element".to_string(),
+///     selector: Some(".intro-element".to_string()),
 ///     content: "This is the first step of the tour.".to_string(),
+///     ..Default::default()
 /// };
 /// ```
 ///
+/// A step without a selector is a floating step, shown as a centered tooltip
+/// without arrow and highlight, e.g. for welcome or summary screens:
+///
+/// ```
+/// use yew_tou_rs::prelude::TourStep;
+///
+/// let welcome = TourStep::floating("Welcome to our app!");
+/// let button = TourStep::new("#feature-button", "Click here to see our main feature.");
+/// ```
+///
 /// # Serialization
 ///
 /// This struct derives `Serialize` and `Deserialize`, allowing it to be easily
-/// converted to and from various data formats like JSON.
+/// converted to and from various data formats like JSON. The lifecycle hooks are
+/// not serialized.
 ///
 /// # Clone and Debug
 ///
 /// `TourStep` can be cloned and printed for debugging purposes.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct TourStep {
+    /// An optional identifier of the step.
+    ///
+    /// Unlike the index, the id stays the same when steps are added or reordered,
+    /// so it is a stable name for the step in analytics.
+    #[serde(default)]
+    pub id: Option<String>,
+
     /// A CSS selector string used to identify the element to highlight for this step.
     ///
     /// This should be a valid CSS selector that uniquely identifies the target element
     /// on the page. For example, "#intro-button" or ".navbar-item:first-child".
-    pub selector: String,
+    /// If `None`, the step is shown as a floating tooltip in the center of the screen.
+    #[serde(default)]
+    pub selector: Option<String>,
 
     /// The content to display for this step of the tour.
     ///
@@ -44,4 +75,91 @@ pub struct TourStep {
     /// instructions to the user.
     /// For Markdown the feature `markdown` must be enabled.
     pub content: String,
+
+    /// The preferred side of the target element to place the tooltip on.
+    ///
+    /// If the tooltip does not fit there, the opposite side is tried next and then
+    /// the perpendicular sides. Defaults to the side with the most space.
+    #[serde(default)]
+    pub placement: Placement,
+
+    /// How the tooltip is aligned along the edge of the target element, e.g. with the
+    /// start of a wide toolbar. Defaults to centering it.
+    #[serde(default)]
+    pub alignment: Alignment,
+
+    /// The maximum width of the tooltip in pixels, overriding the tour's `max_width`.
+    #[serde(default)]
+    pub max_width: Option<u32>,
+
+    /// The maximum height of the step content in pixels, overriding the tour's `max_height`.
+    #[serde(default)]
+    pub max_height: Option<u32>,
+
+    /// What to do if the target element of this step can not be found.
+    ///
+    /// If `None`, the `missing_target` policy of the tour is used.
+    #[serde(default)]
+    pub missing_target: Option<MissingTargetPolicy>,
+
+    /// How many milliseconds to wait for the target element to appear.
+    ///
+    /// Use this for targets which are rendered later, e.g. after data was loaded.
+    /// While waiting, the tooltip shows a loading state. If the target does not
+    /// appear in time, the `missing_target` policy applies. The wait starts over
+    /// each time the step is shown. If `None`, the policy applies right away.
+    #[serde(default)]
+    pub wait_for_target: Option<u32>,
+
+    /// The `TourConfig.version` of the tour which added this step.
+    ///
+    /// With `ReshowPolicy::NewSteps`, only steps added after the version seen by the
+    /// user are shown again. Steps with `None` belong to the initial tour.
+    #[serde(default)]
+    pub since_version: Option<u32>,
+
+    /// Optional callback invoked with the index of the step when it is shown.
+    ///
+    /// It is not called while the step waits for its target, nor for a step which is
+    /// left because its target is missing.
+    #[serde(skip)]
+    pub on_enter: Option<Callback<usize>>,
+
+    /// Optional callback invoked with the index of the step when it is no longer shown,
+    /// if `on_enter` was called for it.
+    #[serde(skip)]
+    pub on_leave: Option<Callback<usize>>,
+
+    /// Optional callback invoked before the tour moves away from this step using the
+    /// tooltip's navigation.
+    ///
+    /// The change only happens once the callback calls `StepTransition::proceed`,
+    /// which allows to veto or delay it. Skipping the tour is not affected.
+    #[serde(skip)]
+    pub before_change: Option<Callback<StepTransition>>,
+}
+
+impl TourStep {
+    /// Creates a step highlighting the element matching the given CSS selector.
+    pub fn new(selector: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            selector: Some(selector.into()),
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a floating step without a target element.
+    pub fn floating(content: impl Into<String>) -> Self {
+        Self {
+            selector: None,
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns `true` if the step has no target element.
+    pub fn is_floating(&self) -> bool {
+        self.selector.is_none()
+    }
 }
@@ -0,0 +1,52 @@
+use yew::Callback;
+
+/// A pending change away from the current step, passed to `TourStep.before_change`.
+///
+/// The transition only happens once `proceed` is called. Call it right away to
+/// allow the change, later to delay it, e.g. until a form is valid, or never to
+/// veto it. Calling `proceed` after the tour moved on in the meantime does nothing.
+///
+/// # Examples
+///
+/// ```
+/// use yew::Callback;
+/// use yew_tou_rs::prelude::{StepTransition, TourStep};
+///
+/// let step = TourStep {
+///     selector: Some("#signup-form".to_string()),
+///     content: "Fill in your name to continue.".to_string(),
+///     before_change: Some(Callback::from(|transition: StepTransition| {
+///         let form_is_valid = true;
+///         if form_is_valid || transition.is_backwards() {
+///             transition.proceed();
+///         }
+///     })),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepTransition {
+    /// The index of the step the tour is leaving.
+    pub from: usize,
+
+    /// The index of the step the tour moves to, or `None` if the tour is completed.
+    pub to: Option<usize>,
+
+    proceed: Callback<()>,
+}
+
+impl StepTransition {
+    pub(crate) fn new(from: usize, to: Option<usize>, proceed: Callback<()>) -> Self {
+        Self { from, to, proceed }
+    }
+
+    /// Returns `true` if the tour moves back to an earlier step.
+    pub fn is_backwards(&self) -> bool {
+        self.to.is_some_and(|to| to < self.from)
+    }
+
+    /// Lets the transition happen.
+    pub fn proceed(&self) {
+        self.proceed.emit(());
+    }
+}
@@ -0,0 +1,238 @@
+use crate::controller::{TourAction, TourController, TourState};
+use crate::registry::{TourRegistry, TourRegistryAction};
+use crate::storage::{TourStorageHandle, reset_all_tours, reset_tour};
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::prelude::*;
+
+/// The tours mounted below a provider with the storage they persist their state in.
+type MountedTours = Rc<RefCell<Vec<(String, TourStorageHandle)>>>;
+
+/// Context shared by a `TourProvider` with the `Tour` it contains and the `use_tour` hook.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TourContext {
+    controller: UseReducerHandle<TourController>,
+    registry: UseReducerHandle<TourRegistry>,
+    storage: Option<TourStorageHandle>,
+    tours: MountedTours,
+}
+
+impl TourContext {
+    pub(crate) fn controller(&self) -> UseReducerHandle<TourController> {
+        self.controller.clone()
+    }
+
+    pub(crate) fn registry(&self) -> UseReducerHandle<TourRegistry> {
+        self.registry.clone()
+    }
+
+    pub(crate) fn storage(&self) -> Option<TourStorageHandle> {
+        self.storage.clone()
+    }
+
+    /// Remembers the storage a tour persists its state in, which may be namespaced or
+    /// configured for the tour, so `use_tour` resets it there.
+    pub(crate) fn mount_tour(&self, id: &str, storage: TourStorageHandle) {
+        let mut tours = self.tours.borrow_mut();
+        tours.retain(|(mounted, _)| mounted != id);
+        tours.push((id.to_string(), storage));
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct TourProviderProps {
+    #[prop_or_default]
+    pub children: Html,
+
+    /// The registry of named tours, populated with their priorities.
+    ///
+    /// Named tours below the provider only run one at a time. The registry is read
+    /// once when the provider is mounted.
+    #[prop_or_default]
+    pub registry: TourRegistry,
+
+    /// Optional storage for the tours below the provider which have no storage configured.
+    #[prop_or_default]
+    pub storage: Option<TourStorageHandle>,
+
+    /// Optional namespace for the keys of the provider's storage, e.g. built from the
+    /// id of the signed in user, so tour state is not shared between accounts.
+    ///
+    /// If set without a `storage`, the default storage is namespaced.
+    #[prop_or_default]
+    pub namespace: Option<String>,
+}
+
+/// Provides programmatic control over a `Tour` rendered anywhere below it.
+///
+/// Components inside the provider can use the `use_tour` hook to start, stop or
+/// restart the tour, or to jump to a step.
+///
+/// Tours with an `id` below the provider are coordinated by a `TourRegistry`: only one
+/// of them is active at a time and the others are queued by priority until it finishes.
+///
+/// # Examples
+///
+/// ```
+/// use yew::prelude::*;
+/// use yew_tou_rs::prelude::*;
+///
+/// #[function_component(ReplayButton)]
+/// fn replay_button() -> Html {
+///     let tour = use_tour();
+///     let onclick = Callback::from(move |_| tour.restart());
+///     html! { <button {onclick}>{"Replay tour"}</button> }
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     let steps = vec![TourStep {
+///         selector: Some(".intro-element".to_string()),
+///         content: "Welcome to the tour!".to_string(),
+///         ..Default::default()
+///     }];
+///
+///     html! {
+///         <TourProvider>
+///             <h1 class="intro-element">{"Welcome"}</h1>
+///             <ReplayButton />
+///             <Tour steps={steps} auto_start={false} />
+///         </TourProvider>
+///     }
+/// }
+/// ```
+#[function_component(TourProvider)]
+pub fn tour_provider(props: &TourProviderProps) -> Html {
+    let controller = use_reducer(TourController::default);
+    let registry = {
+        let registry = props.registry.clone();
+        use_reducer(move || registry)
+    };
+    let storage = use_memo(
+        (props.storage.clone(), props.namespace.clone()),
+        |(storage, namespace)| match namespace {
+            Some(namespace) => storage
+                .clone()
+                .or_else(TourStorageHandle::default_storage)
+                .map(|storage| storage.namespaced(namespace)),
+            None => storage.clone(),
+        },
+    );
+    let tours = use_memo((), |_| MountedTours::default());
+    let context = TourContext {
+        controller,
+        registry,
+        storage: (*storage).clone(),
+        tours: (*tours).clone(),
+    };
+
+    html! {
+        <ContextProvider<TourContext> context={context}>
+            {props.children.clone()}
+        </ContextProvider<TourContext>>
+    }
+}
+
+/// Handle returned by `use_tour` to control the tour of the surrounding `TourProvider`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UseTourHandle {
+    controller: UseReducerHandle<TourController>,
+    registry: UseReducerHandle<TourRegistry>,
+    storage: Option<TourStorageHandle>,
+    tours: MountedTours,
+}
+
+impl UseTourHandle {
+    /// Starts the active tour at the first step, unless it is already running.
+    ///
+    /// If no named tour is active, the first one rendered takes over.
+    pub fn start(&self) {
+        self.controller.dispatch(TourAction::Start);
+    }
+
+    /// Hides the tour.
+    pub fn stop(&self) {
+        self.controller.dispatch(TourAction::Stop);
+    }
+
+    /// Starts the tour over at the first step.
+    pub fn restart(&self) {
+        self.controller.dispatch(TourAction::Restart);
+    }
+
+    /// Jumps to the step with the given index, showing the tour if it is hidden.
+    pub fn goto(&self, step: usize) {
+        self.controller.dispatch(TourAction::Goto(step));
+    }
+
+    /// Returns the index of the step currently shown, if the tour is running.
+    pub fn current_step(&self) -> Option<usize> {
+        self.controller.current_step()
+    }
+
+    /// Returns the current state of the tour.
+    pub fn state(&self) -> TourState {
+        self.controller.state()
+    }
+
+    /// Starts the named tour with the given id right away, putting an active tour back in the queue.
+    pub fn start_tour(&self, id: impl Into<String>) {
+        self.registry
+            .dispatch(TourRegistryAction::Activate(id.into()));
+        self.controller.dispatch(TourAction::Restart);
+    }
+
+    /// Returns the id of the active named tour.
+    pub fn active_tour(&self) -> Option<String> {
+        self.registry.active().map(str::to_string)
+    }
+
+    /// Forgets the persisted state of the tour with the given id, so it is shown again
+    /// the next time it is mounted.
+    ///
+    /// Tours which were mounted below the provider are reset in the storage they use,
+    /// including their `TourConfig.storage` and `TourConfig.namespace`. Other tours are
+    /// reset in the storage of the provider, or the default storage.
+    pub fn reset_tour(&self, id: &str) {
+        let mounted = self
+            .tours
+            .borrow()
+            .iter()
+            .find(|(mounted, _)| mounted == id)
+            .map(|(_, storage)| storage.clone());
+        if let Some(storage) = mounted.or_else(|| self.storage.clone()) {
+            reset_tour(&storage, id);
+        }
+    }
+
+    /// Forgets the persisted state of all tours in the storage of the provider, or the
+    /// default storage, and of the tours which were mounted below the provider.
+    pub fn reset_all_tours(&self) {
+        if let Some(storage) = &self.storage {
+            reset_all_tours(storage);
+        }
+        for (id, storage) in self.tours.borrow().iter() {
+            reset_tour(storage, id);
+            reset_all_tours(storage);
+        }
+    }
+}
+
+/// Returns a handle to control the tour of the surrounding `TourProvider`.
+///
+/// # Panics
+///
+/// Panics if the component using this hook is not rendered inside a `TourProvider`.
+#[hook]
+pub fn use_tour() -> UseTourHandle {
+    let context =
+        use_context::<TourContext>().expect("use_tour must be used inside a TourProvider");
+    UseTourHandle {
+        controller: context.controller(),
+        registry: context.registry(),
+        storage: context
+            .storage()
+            .or_else(TourStorageHandle::default_storage),
+        tours: context.tours,
+    }
+}
@@ -0,0 +1,267 @@
+use std::rc::Rc;
+use yew::Reducible;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct RegisteredTour {
+    id: String,
+    priority: i32,
+}
+
+/// A registry of named tours which makes sure only one tour is active at a time.
+///
+/// Tours are keyed by their `TourConfig.id`. When a tour asks to run while another
+/// one is active, it is queued and started as soon as the active tour finishes.
+/// Queued tours are started by descending priority, tours with the same priority
+/// in the order they were queued. Tours which were not registered have priority `0`.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::TourRegistry;
+///
+/// let mut registry = TourRegistry::new()
+///     .with_tour("welcome", 10)
+///     .with_tour("editor", 0)
+///     .with_tour("whats-new", 5);
+///
+/// registry.request("editor");
+/// registry.request("welcome");
+/// registry.request("whats-new");
+/// assert_eq!(registry.active(), Some("editor"));
+///
+/// registry.finish("editor");
+/// assert_eq!(registry.active(), Some("welcome"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TourRegistry {
+    tours: Vec<RegisteredTour>,
+    active: Option<String>,
+    queue: Vec<String>,
+}
+
+impl TourRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a tour with the given priority and returns the registry.
+    pub fn with_tour(mut self, id: impl Into<String>, priority: i32) -> Self {
+        self.register(id, priority);
+        self
+    }
+
+    /// Registers a tour with the given priority, replacing the priority of a tour
+    /// registered before with the same id.
+    pub fn register(&mut self, id: impl Into<String>, priority: i32) {
+        let id = id.into();
+        match self.tours.iter_mut().find(|tour| tour.id == id) {
+            Some(tour) => tour.priority = priority,
+            None => self.tours.push(RegisteredTour { id, priority }),
+        }
+    }
+
+    /// Returns the priority of a tour.
+    pub fn priority(&self, id: &str) -> i32 {
+        self.tours
+            .iter()
+            .find(|tour| tour.id == id)
+            .map(|tour| tour.priority)
+            .unwrap_or_default()
+    }
+
+    /// Returns the id of the active tour.
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    /// Returns `true` if the tour with the given id is the active tour.
+    pub fn is_active(&self, id: &str) -> bool {
+        self.active() == Some(id)
+    }
+
+    /// Returns the ids of the queued tours in the order they will be started.
+    pub fn queued(&self) -> Vec<&str> {
+        self.queue.iter().map(String::as_str).collect()
+    }
+
+    /// Asks for a tour to run.
+    ///
+    /// The tour becomes active if no other tour is active, otherwise it is queued.
+    pub fn request(&mut self, id: impl Into<String>) {
+        let id = id.into();
+        if self.active.is_none() {
+            self.active = Some(id);
+        } else if !self.is_active(&id) && !self.queue.contains(&id) {
+            self.enqueue(id);
+        }
+    }
+
+    /// Makes a tour active only if no other tour is active. The tour is not queued otherwise.
+    pub fn claim(&mut self, id: impl Into<String>) {
+        if self.active.is_none() {
+            self.active = Some(id.into());
+        }
+    }
+
+    /// Makes a tour active right away. A different active tour is put back in front of the queue.
+    pub fn activate(&mut self, id: impl Into<String>) {
+        let id = id.into();
+        self.queue.retain(|queued| *queued != id);
+        if let Some(previous) = self.active.replace(id.clone())
+            && previous != id
+        {
+            self.queue.insert(0, previous);
+        }
+    }
+
+    /// Marks a tour as finished, starting the queued tour with the highest priority if it was active.
+    pub fn finish(&mut self, id: &str) {
+        if self.is_active(id) {
+            self.active = (!self.queue.is_empty()).then(|| self.queue.remove(0));
+        } else {
+            self.queue.retain(|queued| queued != id);
+        }
+    }
+
+    fn enqueue(&mut self, id: String) {
+        let priority = self.priority(&id);
+        let index = self
+            .queue
+            .iter()
+            .position(|queued| self.priority(queued) < priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(index, id);
+    }
+}
+
+/// Actions which can be dispatched to a `TourRegistry` used as a Yew reducer.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TourRegistryAction {
+    Request(String),
+    Claim(String),
+    Activate(String),
+    Finish(String),
+}
+
+impl Reducible for TourRegistry {
+    type Action = TourRegistryAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut registry = (*self).clone();
+        match action {
+            TourRegistryAction::Request(id) => registry.request(id),
+            TourRegistryAction::Claim(id) => registry.claim(id),
+            TourRegistryAction::Activate(id) => registry.activate(id),
+            TourRegistryAction::Finish(id) => registry.finish(&id),
+        }
+        registry.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn registry() -> TourRegistry {
+        TourRegistry::new()
+            .with_tour("low", -1)
+            .with_tour("high", 10)
+            .with_tour("medium", 5)
+    }
+
+    #[test]
+    fn test_first_request_becomes_active() {
+        let mut registry = registry();
+        assert_eq!(registry.active(), None);
+        registry.request("low");
+        assert!(registry.is_active("low"));
+        assert!(registry.queued().is_empty());
+    }
+
+    #[rstest]
+    #[case::by_priority(&["low", "medium", "unknown", "high"], &["high", "medium", "unknown"])]
+    #[case::same_priority_in_order(&["high", "a", "b", "c"], &["a", "b", "c"])]
+    #[case::no_duplicates(&["high", "low", "low", "high"], &["low"])]
+    fn test_queue_order(#[case] requests: &[&str], #[case] expected: &[&str]) {
+        let mut registry = registry();
+        for id in requests {
+            registry.request(*id);
+        }
+        assert_eq!(registry.active(), Some(requests[0]));
+        assert_eq!(registry.queued(), expected);
+    }
+
+    #[test]
+    fn test_finish_starts_next() {
+        let mut registry = registry();
+        registry.request("low");
+        registry.request("medium");
+        registry.request("high");
+        registry.finish("low");
+        assert_eq!(registry.active(), Some("high"));
+        registry.finish("high");
+        assert_eq!(registry.active(), Some("medium"));
+        registry.finish("medium");
+        assert_eq!(registry.active(), None);
+    }
+
+    #[test]
+    fn test_finish_queued_removes_it() {
+        let mut registry = registry();
+        registry.request("low");
+        registry.request("medium");
+        registry.finish("medium");
+        assert_eq!(registry.active(), Some("low"));
+        assert!(registry.queued().is_empty());
+    }
+
+    #[test]
+    fn test_finish_unmounted_tours() {
+        let mut registry = registry();
+        registry.request("low");
+        registry.request("medium");
+        registry.request("high");
+        // The queued tour unmounts and never becomes active
+        registry.finish("high");
+        assert_eq!(registry.queued(), vec!["medium"]);
+        // The active tour unmounts and hands its turn over
+        registry.finish("low");
+        assert_eq!(registry.active(), Some("medium"));
+        assert!(registry.queued().is_empty());
+        // Unmounting a tour which never asked for a turn changes nothing
+        registry.finish("unknown");
+        assert_eq!(registry.active(), Some("medium"));
+    }
+
+    #[test]
+    fn test_claim_does_not_queue() {
+        let mut registry = registry();
+        registry.claim("low");
+        registry.claim("high");
+        assert_eq!(registry.active(), Some("low"));
+        assert!(registry.queued().is_empty());
+    }
+
+    #[test]
+    fn test_activate_requeues_previous() {
+        let mut registry = registry();
+        registry.request("low");
+        registry.request("high");
+        registry.activate("medium");
+        assert_eq!(registry.active(), Some("medium"));
+        assert_eq!(registry.queued(), vec!["low", "high"]);
+        registry.activate("high");
+        assert_eq!(registry.active(), Some("high"));
+        assert_eq!(registry.queued(), vec!["medium", "low"]);
+    }
+
+    #[test]
+    fn test_register_replaces_priority() {
+        let mut registry = registry();
+        registry.register("low", 20);
+        assert_eq!(registry.priority("low"), 20);
+        assert_eq!(registry.priority("unknown"), 0);
+    }
+}
@@ -0,0 +1,193 @@
+use super::{MemoryTourStorage, TourStorage, TourStorageHandle};
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+#[derive(Debug)]
+struct ConsentState {
+    inner: TourStorageHandle,
+    granted: Cell<bool>,
+    pending: MemoryTourStorage,
+    removed: RefCell<HashSet<String>>,
+}
+
+/// A `TourStorage` which only writes to its backend once the user consented.
+///
+/// Until consent is granted, tour state is held in memory and nothing is written to
+/// the backend. Granting consent flushes the state to the backend. Values already in
+/// the backend, e.g. from an earlier session with consent, are still read.
+///
+/// Clones share the consent, so keep a clone to grant consent later.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{ConsentStorage, MemoryTourStorage, TourStorage, TourStorageHandle};
+///
+/// let backend = TourStorageHandle::new(MemoryTourStorage::default());
+/// let consent = ConsentStorage::new(backend.clone());
+/// let storage = TourStorageHandle::new(consent.clone());
+///
+/// storage.set("tour-record", "{}").unwrap();
+/// assert_eq!(backend.get("tour-record"), None);
+///
+/// consent.grant();
+/// assert_eq!(backend.get("tour-record"), Some("{}".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConsentStorage(Rc<ConsentState>);
+
+impl ConsentStorage {
+    /// Wraps a backend, without consent to write to it yet.
+    pub fn new(inner: TourStorageHandle) -> Self {
+        Self(Rc::new(ConsentState {
+            inner,
+            granted: Cell::new(false),
+            pending: MemoryTourStorage::default(),
+            removed: RefCell::default(),
+        }))
+    }
+
+    /// Wraps a backend the user already consented to write to.
+    pub fn granted(inner: TourStorageHandle) -> Self {
+        let storage = Self::new(inner);
+        storage.0.granted.set(true);
+        storage
+    }
+
+    /// Returns `true` if the user consented to writing to the backend.
+    pub fn has_consent(&self) -> bool {
+        self.0.granted.get()
+    }
+
+    /// Grants consent and writes the state held in memory to the backend.
+    pub fn grant(&self) {
+        let state = &self.0;
+        state.granted.set(true);
+        for key in state.removed.take() {
+            state.inner.remove(&key);
+        }
+        for key in state.pending.keys() {
+            if let Some(value) = state.pending.get(&key) {
+                let _ = state.inner.set(&key, &value);
+            }
+            state.pending.remove(&key);
+        }
+    }
+
+    /// Revokes consent. Later changes are held in memory again, while the state
+    /// already written to the backend stays there.
+    pub fn revoke(&self) {
+        self.0.granted.set(false);
+    }
+}
+
+impl TourStorage for ConsentStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        let state = &self.0;
+        if state.removed.borrow().contains(key) {
+            return None;
+        }
+        state.pending.get(key).or_else(|| state.inner.get(key))
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        let state = &self.0;
+        if state.granted.get() {
+            return state.inner.set(key, value);
+        }
+        state.removed.borrow_mut().remove(key);
+        state.pending.set(key, value)
+    }
+
+    fn remove(&self, key: &str) {
+        let state = &self.0;
+        if state.granted.get() {
+            state.inner.remove(key);
+        } else {
+            state.pending.remove(key);
+            state.removed.borrow_mut().insert(key.to_string());
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let state = &self.0;
+        let removed = state.removed.borrow();
+        let mut keys: Vec<String> = state
+            .inner
+            .keys()
+            .into_iter()
+            .filter(|key| !removed.contains(key) && state.pending.get(key).is_none())
+            .collect();
+        keys.extend(state.pending.keys());
+        keys
+    }
+
+    fn key_from_backend(&self, key: &str) -> Option<String> {
+        self.0.inner.key_from_backend(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend() -> TourStorageHandle {
+        TourStorageHandle::new(MemoryTourStorage::default())
+    }
+
+    #[test]
+    fn test_nothing_written_without_consent() {
+        let backend = backend();
+        let storage = ConsentStorage::new(backend.clone());
+        storage.set("a", "1").unwrap();
+        assert_eq!(storage.get("a"), Some("1".to_string()));
+        assert!(backend.keys().is_empty());
+        assert!(!storage.has_consent());
+    }
+
+    #[test]
+    fn test_grant_flushes() {
+        let backend = backend();
+        backend.set("old", "1").unwrap();
+        backend.set("stale", "1").unwrap();
+        let storage = ConsentStorage::new(backend.clone());
+        storage.set("new", "2").unwrap();
+        storage.remove("stale");
+        assert_eq!(storage.get("stale"), None);
+        assert_eq!(backend.get("stale"), Some("1".to_string()));
+
+        storage.grant();
+        assert_eq!(backend.get("new"), Some("2".to_string()));
+        assert_eq!(backend.get("old"), Some("1".to_string()));
+        assert_eq!(backend.get("stale"), None);
+
+        storage.set("later", "3").unwrap();
+        assert_eq!(backend.get("later"), Some("3".to_string()));
+    }
+
+    #[test]
+    fn test_revoke_holds_changes_in_memory() {
+        let backend = backend();
+        let storage = ConsentStorage::granted(backend.clone());
+        storage.set("a", "1").unwrap();
+        storage.revoke();
+        storage.set("a", "2").unwrap();
+        assert_eq!(storage.get("a"), Some("2".to_string()));
+        assert_eq!(backend.get("a"), Some("1".to_string()));
+    }
+
+    #[test]
+    fn test_keys() {
+        let backend = backend();
+        backend.set("a", "1").unwrap();
+        backend.set("b", "1").unwrap();
+        let storage = ConsentStorage::new(backend);
+        storage.remove("a");
+        storage.set("b", "2").unwrap();
+        storage.set("c", "3").unwrap();
+        let mut keys = storage.keys();
+        keys.sort();
+        assert_eq!(keys, vec!["b".to_string(), "c".to_string()]);
+    }
+}
@@ -0,0 +1,205 @@
+//! Cookies as storage for tour state, readable by a server-side renderer.
+
+#[cfg(feature = "storage")]
+use super::TourStorage;
+
+// Characters kept as they are when encoding cookie names and values
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~')
+}
+
+/// Percent-encodes a cookie name or value like `encodeURIComponent`, e.g. to set
+/// the cookies of `CookieTourStorage` on the server.
+pub fn encode_cookie_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match is_unreserved(byte) {
+            true => (byte as char).to_string(),
+            false => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+// Decodes a percent-encoded cookie name or value, keeping invalid escapes as they are
+fn decode_cookie_component(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| value.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parses a `Cookie` header, or `document.cookie`, into decoded name-value pairs.
+///
+/// This works without wasm, e.g. in a server-side renderer.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::parse_cookies;
+///
+/// let cookies = parse_cookies("theme=dark; tour-record=%7B%7D");
+/// assert_eq!(cookies[1], ("tour-record".to_string(), "{}".to_string()));
+/// ```
+pub fn parse_cookies(header: &str) -> Vec<(String, String)> {
+    header
+        .split(';')
+        .filter_map(|cookie| cookie.split_once('='))
+        .map(|(name, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            (
+                decode_cookie_component(name.trim()),
+                decode_cookie_component(value),
+            )
+        })
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
+/// A `TourStorage` persisting tour state in cookies, which are sent to the server.
+///
+/// Names and values are percent-encoded. Cookies are limited to about 4 KB each.
+#[cfg(feature = "storage")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieTourStorage {
+    path: String,
+    max_age: u32,
+}
+
+#[cfg(feature = "storage")]
+impl Default for CookieTourStorage {
+    fn default() -> Self {
+        Self {
+            path: "/".to_string(),
+            max_age: 365 * 24 * 60 * 60,
+        }
+    }
+}
+
+#[cfg(feature = "storage")]
+impl CookieTourStorage {
+    /// Creates a storage with cookies for the whole site, kept for a year.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path of the cookies.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets how many seconds the cookies are kept.
+    pub fn with_max_age(mut self, max_age: u32) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    fn document() -> Option<web_sys::HtmlDocument> {
+        use wasm_bindgen::JsCast;
+
+        web_sys::window()?.document()?.dyn_into().ok()
+    }
+
+    fn cookies() -> Vec<(String, String)> {
+        Self::document()
+            .and_then(|document| document.cookie().ok())
+            .map(|cookies| parse_cookies(&cookies))
+            .unwrap_or_default()
+    }
+
+    fn write(&self, key: &str, value: &str, max_age: u32) -> Result<(), String> {
+        let document = Self::document().ok_or_else(|| "Failed to get document".to_string())?;
+        let cookie = format!(
+            "{}={}; path={}; max-age={}; SameSite=Lax",
+            encode_cookie_component(key),
+            encode_cookie_component(value),
+            self.path,
+            max_age
+        );
+        document.set_cookie(&cookie).map_err(|e| {
+            e.as_string()
+                .unwrap_or_else(|| "Failed to set cookie".to_string())
+        })
+    }
+}
+
+#[cfg(feature = "storage")]
+impl TourStorage for CookieTourStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        Self::cookies()
+            .into_iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        self.write(key, value, self.max_age)
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = self.write(key, "", 0);
+    }
+
+    fn keys(&self) -> Vec<String> {
+        Self::cookies().into_iter().map(|(name, _)| name).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::plain("tour-record", "tour-record")]
+    #[case::namespace("alice:tour", "alice%3Atour")]
+    #[case::json(r#"{"a":1}"#, "%7B%22a%22%3A1%7D")]
+    #[case::separators("a; b,c", "a%3B%20b%2Cc")]
+    #[case::unicode("ü", "%C3%BC")]
+    fn test_encode_decode(#[case] value: &str, #[case] encoded: &str) {
+        assert_eq!(encode_cookie_component(value), encoded);
+        assert_eq!(decode_cookie_component(encoded), value);
+    }
+
+    #[rstest]
+    #[case::invalid_escape("100%", "100%")]
+    #[case::invalid_hex("%zz", "%zz")]
+    fn test_decode_keeps_invalid_escapes(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(decode_cookie_component(value), expected);
+    }
+
+    #[rstest]
+    #[case::empty("", &[])]
+    #[case::single("a=1", &[("a", "1")])]
+    #[case::spaces(" a = 1 ;b=2", &[("a", "1"), ("b", "2")])]
+    #[case::quoted(r#"a="1""#, &[("a", "1")])]
+    #[case::without_value("flag; a=1", &[("a", "1")])]
+    #[case::equals_in_value("a=x=y", &[("a", "x=y")])]
+    fn test_parse_cookies(#[case] header: &str, #[case] expected: &[(&str, &str)]) {
+        let expected: Vec<(String, String)> = expected
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        assert_eq!(parse_cookies(header), expected);
+    }
+}
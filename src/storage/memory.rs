@@ -0,0 +1,84 @@
+use super::{TourStorage, parse_cookies};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// A `TourStorage` keeping tour state in memory.
+///
+/// The state is lost when the page is reloaded. Useful for tests and for
+/// tours which should only be hidden for the current session of the app.
+#[derive(Debug, Default)]
+pub struct MemoryTourStorage {
+    values: RefCell<HashMap<String, String>>,
+}
+
+impl TourStorage for MemoryTourStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        self.values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) {
+        self.values.borrow_mut().remove(key);
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.values.borrow().keys().cloned().collect()
+    }
+}
+
+impl MemoryTourStorage {
+    /// Creates a storage holding the cookies of a `Cookie` header, as written by
+    /// `CookieTourStorage`.
+    ///
+    /// A server-side renderer can read the tour state from it to decide whether to
+    /// render a tour at all, so it does not flash during hydration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yew_tou_rs::prelude::{DisplayFrequency, MemoryTourStorage, TourRecord, TourStorageHandle};
+    ///
+    /// let header = "tour-record=%7B%22status%22%3A%22dismissed%22%7D";
+    /// let storage = TourStorageHandle::new(MemoryTourStorage::from_cookies(header));
+    /// let record = TourRecord::load(&storage, "tour");
+    /// assert!(!DisplayFrequency::default().should_show(&record, 0));
+    /// ```
+    pub fn from_cookies(header: &str) -> Self {
+        let storage = Self::default();
+        for (name, value) in parse_cookies(header) {
+            let _ = storage.set(&name, &value);
+        }
+        storage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_get_remove() {
+        let storage = MemoryTourStorage::default();
+        assert_eq!(storage.get("key"), None);
+        storage.set("key", "1").unwrap();
+        storage.set("key", "2").unwrap();
+        assert_eq!(storage.get("key"), Some("2".to_string()));
+        assert_eq!(storage.keys(), vec!["key".to_string()]);
+        storage.remove("key");
+        assert_eq!(storage.get("key"), None);
+        assert!(storage.keys().is_empty());
+    }
+
+    #[test]
+    fn test_from_cookies() {
+        let storage = MemoryTourStorage::from_cookies("alice%3Atour-record=%7B%7D; theme=dark");
+        assert_eq!(storage.get("alice:tour-record"), Some("{}".to_string()));
+        assert_eq!(storage.get("theme"), Some("dark".to_string()));
+    }
+}
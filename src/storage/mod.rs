@@ -0,0 +1,266 @@
+//! Persistence of tour state.
+//!
+//! Tours persist their state through a `TourStorage` backend. The built-in backends
+//! are `MemoryTourStorage` and, with the `storage` feature, `LocalTourStorage`,
+//! `SessionTourStorage` and `CookieTourStorage`. `NamespacedStorage` keeps the keys
+//! of several users or apps apart in one backend, `ConsentStorage` holds the state in
+//! memory until the user consents to persisting it. Apps can implement `TourStorage`
+//! for their own backends.
+
+mod consent;
+mod cookie;
+mod memory;
+mod namespaced;
+#[cfg(feature = "storage")]
+mod web;
+
+pub use consent::ConsentStorage;
+#[cfg(feature = "storage")]
+pub use cookie::CookieTourStorage;
+pub use cookie::{encode_cookie_component, parse_cookies};
+pub use memory::MemoryTourStorage;
+pub use namespaced::NamespacedStorage;
+#[cfg(feature = "storage")]
+pub use web::{LocalTourStorage, SessionTourStorage};
+
+use crate::models::TourRecord;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::rc::Rc;
+
+/// A key-value store for persisting tour state.
+///
+/// Values are JSON strings. Implement this trait to persist tours in a custom
+/// backend, for example per-user state synced to a server.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{MemoryTourStorage, TourStorage};
+///
+/// let storage = MemoryTourStorage::default();
+/// storage.set("main-tour-show", "false").unwrap();
+/// assert_eq!(storage.get("main-tour-show"), Some("false".to_string()));
+/// ```
+pub trait TourStorage {
+    /// Returns the value stored for the key.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores a value for the key.
+    fn set(&self, key: &str, value: &str) -> Result<(), String>;
+
+    /// Removes the value stored for the key.
+    fn remove(&self, key: &str);
+
+    /// Returns all stored keys.
+    ///
+    /// Used by `ConsentStorage` to write the state held in memory. Backends which can
+    /// not list their keys return none, the default.
+    fn keys(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns the key of this storage for a key of the underlying backend, e.g. the key
+    /// reported by a browser `storage` event, or `None` if the key does not belong to it.
+    ///
+    /// Storages changing the keys of the storage they wrap, like `NamespacedStorage`,
+    /// map them back. The key is returned as it is by default.
+    fn key_from_backend(&self, key: &str) -> Option<String> {
+        Some(key.to_string())
+    }
+}
+
+/// The key listing the ids of the tours persisted in a storage.
+///
+/// The storage is usually shared with the app, so tours are only reset by their ids
+/// and other keys are never touched.
+const TOURS_KEY: &str = "yew-tou-rs:tours";
+
+/// Returns the ids of the tours persisted in the storage.
+fn persisted_tours(storage: &TourStorageHandle) -> Vec<String> {
+    storage.get_value(TOURS_KEY).unwrap_or_default()
+}
+
+/// Adds the tour with the given id to the tours persisted in the storage.
+pub(crate) fn add_persisted_tour(storage: &TourStorageHandle, id: &str) -> Result<(), String> {
+    let mut ids = persisted_tours(storage);
+    if ids.iter().any(|persisted| persisted == id) {
+        return Ok(());
+    }
+    ids.push(id.to_string());
+    storage.set_value(TOURS_KEY, &ids)
+}
+
+/// Forgets the persisted state of the tour with the given id, so it is shown again
+/// the next time it is mounted.
+///
+/// This also removes the `{id}-show` key of earlier versions.
+pub fn reset_tour(storage: &TourStorageHandle, id: &str) {
+    storage.remove(&TourRecord::key(id));
+    storage.remove(&TourRecord::legacy_key(id));
+
+    let mut ids = persisted_tours(storage);
+    if ids.iter().any(|persisted| persisted == id) {
+        ids.retain(|persisted| persisted != id);
+        if ids.is_empty() {
+            storage.remove(TOURS_KEY);
+        } else {
+            let _ = storage.set_value(TOURS_KEY, &ids);
+        }
+    }
+}
+
+/// Forgets the persisted state of all tours in the storage.
+///
+/// Only the tours recorded in this storage are reset, other keys of the storage are
+/// left alone. Tours only known from the keys of earlier versions are reset with
+/// `reset_tour`.
+pub fn reset_all_tours(storage: &TourStorageHandle) {
+    for id in persisted_tours(storage) {
+        reset_tour(storage, &id);
+    }
+    storage.remove(TOURS_KEY);
+}
+
+/// A shared handle to a `TourStorage` backend, used to pass it through
+/// `TourConfig.storage` or the `storage` of a `TourProvider`.
+///
+/// Handles are equal if they point to the same backend.
+#[derive(Clone)]
+pub struct TourStorageHandle(Rc<dyn TourStorage>);
+
+impl TourStorageHandle {
+    /// Wraps a storage backend.
+    pub fn new(storage: impl TourStorage + 'static) -> Self {
+        Self(Rc::new(storage))
+    }
+
+    /// Returns the backend used when no storage is configured.
+    ///
+    /// This is `LocalTourStorage` with the `storage` feature, and no storage without it.
+    pub fn default_storage() -> Option<Self> {
+        #[cfg(feature = "storage")]
+        {
+            Some(Self::new(LocalTourStorage))
+        }
+        #[cfg(not(feature = "storage"))]
+        {
+            None
+        }
+    }
+
+    /// Returns a handle to the same backend with all keys prefixed by the namespace.
+    pub fn namespaced(&self, namespace: impl AsRef<str>) -> Self {
+        Self::new(NamespacedStorage::new(self.clone(), namespace))
+    }
+
+    /// Reads and deserializes the JSON value stored for the key.
+    pub fn get_value<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.0
+            .get(key)
+            .and_then(|value| serde_json::from_str(&value).ok())
+    }
+
+    /// Serializes the value to JSON and stores it for the key.
+    pub fn set_value<T: Serialize>(&self, key: &str, value: &T) -> Result<(), String> {
+        let value = serde_json::to_string(value).map_err(|e| e.to_string())?;
+        self.0.set(key, &value)
+    }
+}
+
+impl std::ops::Deref for TourStorageHandle {
+    type Target = dyn TourStorage;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for TourStorageHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for TourStorageHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TourStorageHandle").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_values() {
+        let storage = TourStorageHandle::new(MemoryTourStorage::default());
+        storage.set_value("tour-show", &false).unwrap();
+        assert_eq!(storage.get("tour-show"), Some("false".to_string()));
+        assert_eq!(storage.get_value::<bool>("tour-show"), Some(false));
+        assert_eq!(storage.get_value::<bool>("unknown"), None);
+    }
+
+    #[test]
+    fn test_invalid_json_is_ignored() {
+        let storage = TourStorageHandle::new(MemoryTourStorage::default());
+        storage.set("tour-show", "not json").unwrap();
+        assert_eq!(storage.get_value::<bool>("tour-show"), None);
+    }
+
+    #[test]
+    fn test_reset_tours() {
+        let storage = TourStorageHandle::new(MemoryTourStorage::default());
+        for id in ["welcome", "editor"] {
+            TourRecord::default().save(&storage, id).unwrap();
+        }
+        storage.set("welcome-show", "false").unwrap();
+
+        reset_tour(&storage, "welcome");
+        assert_eq!(storage.get("welcome-record"), None);
+        assert_eq!(storage.get("welcome-show"), None);
+        assert!(storage.get("editor-record").is_some());
+        assert_eq!(persisted_tours(&storage), vec!["editor".to_string()]);
+
+        reset_all_tours(&storage);
+        assert!(storage.keys().is_empty());
+    }
+
+    #[test]
+    fn test_reset_keeps_keys_of_the_app() {
+        let storage = TourStorageHandle::new(MemoryTourStorage::default());
+        TourRecord::default().save(&storage, "welcome").unwrap();
+        for key in ["foo-record", "foo-show", "foo-completed", "settings"] {
+            storage.set(key, "false").unwrap();
+        }
+
+        reset_all_tours(&storage);
+        let mut keys = storage.keys();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec!["foo-completed", "foo-record", "foo-show", "settings"]
+        );
+    }
+
+    #[test]
+    fn test_reset_legacy_tour() {
+        let storage = TourStorageHandle::new(MemoryTourStorage::default());
+        storage.set("welcome-show", "false").unwrap();
+
+        reset_tour(&storage, "welcome");
+        assert!(storage.keys().is_empty());
+        assert_eq!(TourRecord::load(&storage, "welcome"), TourRecord::default());
+    }
+
+    #[test]
+    fn test_handles_equal_by_backend() {
+        let storage = TourStorageHandle::new(MemoryTourStorage::default());
+        assert_eq!(storage, storage.clone());
+        assert_ne!(
+            storage,
+            TourStorageHandle::new(MemoryTourStorage::default())
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use super::{TourStorage, TourStorageHandle};
+
+/// A `TourStorage` keeping the keys of a namespace apart in a shared backend.
+///
+/// Keys are prefixed with `{namespace}:`, e.g. to keep the tour state of different
+/// user accounts or apps on the same machine apart.
+///
+/// # Examples
+///
+/// ```
+/// use yew_tou_rs::prelude::{MemoryTourStorage, NamespacedStorage, TourStorage, TourStorageHandle};
+///
+/// let shared = TourStorageHandle::new(MemoryTourStorage::default());
+/// let alice = NamespacedStorage::new(shared.clone(), "alice");
+/// alice.set("tour-record", "{}").unwrap();
+/// assert_eq!(shared.get("alice:tour-record"), Some("{}".to_string()));
+/// assert_eq!(alice.keys(), vec!["tour-record".to_string()]);
+/// ```
+#[derive(Debug)]
+pub struct NamespacedStorage {
+    inner: TourStorageHandle,
+    prefix: String,
+}
+
+impl NamespacedStorage {
+    /// Wraps a storage, prefixing all keys with the namespace.
+    pub fn new(inner: TourStorageHandle, namespace: impl AsRef<str>) -> Self {
+        Self {
+            inner,
+            prefix: format!("{}:", namespace.as_ref()),
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+impl TourStorage for NamespacedStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.inner.get(&self.key(key))
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        self.inner.set(&self.key(key), value)
+    }
+
+    fn remove(&self, key: &str) {
+        self.inner.remove(&self.key(key))
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.inner
+            .keys()
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&self.prefix).map(str::to_string))
+            .collect()
+    }
+
+    fn key_from_backend(&self, key: &str) -> Option<String> {
+        self.inner
+            .key_from_backend(key)?
+            .strip_prefix(&self.prefix)
+            .map(str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryTourStorage;
+
+    #[test]
+    fn test_namespaces_are_separate() {
+        let shared = TourStorageHandle::new(MemoryTourStorage::default());
+        let alice = NamespacedStorage::new(shared.clone(), "alice");
+        let bob = NamespacedStorage::new(shared.clone(), "bob");
+
+        alice.set("tour-record", "1").unwrap();
+        assert_eq!(alice.get("tour-record"), Some("1".to_string()));
+        assert_eq!(bob.get("tour-record"), None);
+        assert_eq!(bob.keys(), Vec::<String>::new());
+
+        alice.remove("tour-record");
+        assert_eq!(shared.get("alice:tour-record"), None);
+    }
+
+    #[test]
+    fn test_key_from_backend() {
+        let shared = TourStorageHandle::new(MemoryTourStorage::default());
+        let alice = NamespacedStorage::new(shared.clone(), "alice");
+        assert_eq!(
+            alice.key_from_backend("alice:tour-record").as_deref(),
+            Some("tour-record")
+        );
+        assert_eq!(alice.key_from_backend("malice:tour-record"), None);
+        assert_eq!(alice.key_from_backend("tour-record"), None);
+
+        let team = NamespacedStorage::new(TourStorageHandle::new(alice), "team");
+        assert_eq!(
+            team.key_from_backend("alice:team:tour-record").as_deref(),
+            Some("tour-record")
+        );
+        assert_eq!(team.key_from_backend("team:tour-record"), None);
+    }
+}
@@ -0,0 +1,71 @@
+use super::TourStorage;
+use gloo_storage::Storage;
+
+fn get(storage: web_sys::Storage, key: &str) -> Option<String> {
+    storage.get_item(key).ok().flatten()
+}
+
+fn set(storage: web_sys::Storage, key: &str, value: &str) -> Result<(), String> {
+    storage.set_item(key, value).map_err(|e| {
+        e.as_string()
+            .unwrap_or_else(|| "Failed to set item".to_string())
+    })
+}
+
+fn remove(storage: web_sys::Storage, key: &str) {
+    let _ = storage.remove_item(key);
+}
+
+fn keys(storage: web_sys::Storage) -> Vec<String> {
+    let length = storage.length().unwrap_or_default();
+    (0..length)
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .collect()
+}
+
+/// A `TourStorage` persisting tour state in the browser's `localStorage`.
+///
+/// This is the default storage with the `storage` feature.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalTourStorage;
+
+impl TourStorage for LocalTourStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        get(gloo_storage::LocalStorage::raw(), key)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        set(gloo_storage::LocalStorage::raw(), key, value)
+    }
+
+    fn remove(&self, key: &str) {
+        remove(gloo_storage::LocalStorage::raw(), key)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        keys(gloo_storage::LocalStorage::raw())
+    }
+}
+
+/// A `TourStorage` persisting tour state in the browser's `sessionStorage`,
+/// which is cleared when the browser tab is closed.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SessionTourStorage;
+
+impl TourStorage for SessionTourStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        get(gloo_storage::SessionStorage::raw(), key)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        set(gloo_storage::SessionStorage::raw(), key, value)
+    }
+
+    fn remove(&self, key: &str) {
+        remove(gloo_storage::SessionStorage::raw(), key)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        keys(gloo_storage::SessionStorage::raw())
+    }
+}
@@ -1,12 +1,17 @@
-use crate::components::{Content, Navigation, Progress, Selection, StepInfo};
-use crate::models::{Rect, TourConfig};
-use crate::utils::calculate_arrow_position;
-#[cfg(feature = "storage")]
-use gloo_storage::{LocalStorage, Storage};
+use crate::components::{Content, Loading, Navigation, Progress, Selection, StepInfo};
+use crate::controller::{StepTarget, TourAction, TourController};
+use crate::models::{
+    ARROW_SIZE, MissingTargetPolicy, Placement, Rect, StepTransition, TourConfig, TourError,
+    TourEvent, TourRecord, TourStatus, TourStep, get_scroll_offsets,
+};
+use crate::provider::TourContext;
+use crate::registry::TourRegistryAction;
+use crate::storage::TourStorageHandle;
+use crate::utils::{LayoutObserver, TooltipPosition, compute_position};
 use web_sys::ScrollToOptions;
 use yew::prelude::*;
 
-pub(crate) const ARROW_SIZE: i32 = 10;
+// Default maximum width of the tooltip, and its size until it was measured
 pub(crate) const TOOLTIP_WIDTH: i32 = 300;
 pub(crate) const TOOLTIP_HEIGHT: i32 = 230;
 
@@ -29,6 +34,28 @@ fn window_width() -> i32 {
         .unwrap() as i32
 }
 
+// The visible part of the page, in the document coordinates of the element rects
+fn viewport() -> Rect {
+    let (scroll_x, scroll_y) = get_scroll_offsets().unwrap_or_default();
+    Rect {
+        x: scroll_x as i32,
+        y: scroll_y as i32,
+        width: window_width(),
+        height: window_height(),
+    }
+}
+
+// Zero-sized rectangle in the center of the viewport, used as the target of floating steps
+fn viewport_center() -> Rect {
+    let viewport = viewport();
+    Rect {
+        x: viewport.x + viewport.width / 2,
+        y: viewport.y + viewport.height / 2,
+        width: 0,
+        height: 0,
+    }
+}
+
 // New function to scroll the selected element into view
 fn scroll_into_view(rect: &Rect) {
     let window = web_sys::window().unwrap();
@@ -39,113 +66,688 @@ fn scroll_into_view(rect: &Rect) {
     window.scroll_to_with_scroll_to_options(&options);
 }
 
+// Place the arrow at its offset along the edge of the tooltip, so it points at the target
+fn arrow_style(position: &TooltipPosition) -> String {
+    let Some(arrow_offset) = position.arrow_offset else {
+        return "display: inherit;".to_string();
+    };
+    let offset = arrow_offset - ARROW_SIZE / 2;
+    match position.placement {
+        Placement::Left | Placement::Right => format!(
+            "display: inherit; top: {}px; bottom: auto; margin-top: 0;",
+            offset
+        ),
+        _ => format!(
+            "display: inherit; left: {}px; right: auto; margin-left: 0;",
+            offset
+        ),
+    }
+}
+
+// Resolve the maximum width of the tooltip and the maximum height of its content: the step's
+// limits take precedence over the tour's, the size middleware can limit them further. The size
+// middleware limits the whole tooltip, so the height of everything but the content is subtracted.
+fn max_size(
+    step: &TourStep,
+    config: &TourConfig,
+    position: Option<&TooltipPosition>,
+    chrome_height: i32,
+) -> (u32, Option<u32>) {
+    let max_width = step
+        .max_width
+        .or(config.max_width)
+        .unwrap_or(TOOLTIP_WIDTH as u32);
+    let max_height = step.max_height.or(config.max_height);
+    match position {
+        Some(position) => (
+            position
+                .max_width
+                .map_or(max_width, |limit| max_width.min(limit as u32)),
+            match position.max_height {
+                Some(limit) => {
+                    let limit = (limit - chrome_height).max(0) as u32;
+                    Some(max_height.map_or(limit, |max| max.min(limit)))
+                }
+                None => max_height,
+            },
+        ),
+        None => (max_width, max_height),
+    }
+}
+
+// Map an index into the steps shown by the controller to the index in `TourConfig.steps`
+fn config_index(step_indices: &[usize], index: usize) -> usize {
+    step_indices.get(index).copied().unwrap_or(index)
+}
+
+// Find the index into the steps shown by the controller of a step in `TourConfig.steps`
+fn shown_index(step_indices: &[usize], index: usize) -> Option<usize> {
+    step_indices.iter().position(|shown| *shown == index)
+}
+
+// Update the persisted record of the tour
+fn update_record(
+    storage: &Option<TourStorageHandle>,
+    id: &str,
+    update: impl FnOnce(&mut TourRecord),
+) {
+    if let Some(storage) = storage {
+        let mut record = TourRecord::load(storage, id);
+        update(&mut record);
+        let _ = record.save(storage, id);
+    }
+}
+
 #[function_component(Tour)]
 pub fn tour(config: &TourConfig) -> Html {
     let id = config.id.clone().unwrap_or_else(|| "tour".to_string());
-    let on_close = config.on_close.clone();
-
-    let show_tour = {
-        #[cfg(feature = "storage")]
-        let default_show = LocalStorage::get(format!("{}-show", id)).unwrap_or_else(|_| true);
-        #[cfg(not(feature = "storage"))]
-        let default_show = true;
-        use_state(|| default_show)
+
+    let context = use_context::<TourContext>();
+
+    // Persist the tour state in the configured storage, the provider's storage or the default storage.
+    // Storage handles compare by pointer, so the handle is only created again when one of them changes.
+    let storage = (*use_memo(
+        (
+            config.storage.clone(),
+            context.as_ref().and_then(TourContext::storage),
+            config.namespace.clone(),
+        ),
+        |(storage, context_storage, namespace)| {
+            storage
+                .clone()
+                .or_else(|| context_storage.clone())
+                .or_else(TourStorageHandle::default_storage)
+                .map(|storage| match namespace {
+                    Some(namespace) => storage.namespaced(namespace),
+                    None => storage,
+                })
+        },
+    ))
+    .clone();
+    let clock = config.clock.clone().unwrap_or_default();
+
+    // Let the provider know where to reset the tour
+    {
+        let context = context.clone();
+        use_effect_with((id.clone(), storage.clone()), move |(id, storage)| {
+            if let Some((context, storage)) = context.zip(storage.clone()) {
+                context.mount_tour(id, storage);
+            }
+        });
+    }
+
+    // Decide from the previous runs whether the tour is due to be shown. The record is only
+    // loaded again for another tour or storage, not on every render.
+    let record = use_memo((id.clone(), storage.clone()), |(id, storage)| {
+        storage
+            .as_ref()
+            .map(|storage| TourRecord::load(storage, id))
+            .unwrap_or_default()
+    });
+    let default_show = config.frequency.should_show(&record, clock.now());
+
+    // Show a finished tour again once its version was raised, decided when the tour is mounted
+    let outdated = *use_state(|| record.is_finished() && record.version < config.version);
+    // The controller runs on the steps shown, events and records refer to `config.steps`
+    let step_indices = if outdated {
+        config.reshow.step_indices(&config.steps, record.version)
+    } else {
+        (0..config.steps.len()).collect()
     };
-    let current_step = use_state(|| 0usize);
+    let steps: Vec<TourStep> = step_indices
+        .iter()
+        .map(|index| config.steps[*index].clone())
+        .collect();
+    // Resume an interrupted tour at the last viewed step, found by its id or index. It is
+    // resumed whatever the display frequency, without counting as shown again.
+    let resume_step = Some(&record)
+        .filter(|_| config.resume)
+        .and_then(|record| record.resume_step_in(&config.steps))
+        .and_then(|index| shown_index(&step_indices, index));
+    let auto_start = config.auto_start
+        && (default_show || outdated || resume_step.is_some())
+        && !steps.is_empty();
+    let start_step = resume_step.unwrap_or(0);
+    let step_count = steps.len();
 
-    if !*show_tour {
-        return html! {};
+    let local_controller = use_reducer(move || {
+        let mut controller = TourController::new(step_count);
+        if auto_start {
+            controller.start_at(start_step);
+        }
+        controller
+    });
+    let controller = context
+        .as_ref()
+        .map(TourContext::controller)
+        .unwrap_or(local_controller);
+
+    // Named tours below a provider take turns through its registry
+    let registry = context
+        .as_ref()
+        .filter(|_| config.id.is_some())
+        .map(TourContext::registry);
+    let owns_controller = registry
+        .as_ref()
+        .is_none_or(|registry| registry.is_active(&id));
+
+    // Tell the controller how many steps this tour has while it drives it
+    {
+        let controller = controller.clone();
+        use_effect_with(
+            (step_count, owns_controller),
+            move |(step_count, owns_controller)| {
+                if *owns_controller {
+                    controller.dispatch(TourAction::SetStepCount(*step_count));
+                }
+            },
+        );
+    }
+    // Start the tour when it is mounted, or ask the registry for a turn. A tour which
+    // unmounts gives up its turn or its place in the queue, and stops the shared controller.
+    {
+        let controller = controller.clone();
+        let registry = registry.clone();
+        let id = id.clone();
+        let drives_shared_controller = use_mut_ref(|| false);
+        *drives_shared_controller.borrow_mut() = owns_controller && context.is_some();
+        use_effect_with((), move |_| {
+            if auto_start {
+                match &registry {
+                    Some(registry) => registry.dispatch(TourRegistryAction::Request(id.clone())),
+                    None => controller.dispatch(TourAction::StartAt(start_step)),
+                }
+            }
+            move || {
+                if *drives_shared_controller.borrow() {
+                    controller.dispatch(TourAction::Stop);
+                }
+                if let Some(registry) = registry {
+                    registry.dispatch(TourRegistryAction::Finish(id));
+                }
+            }
+        });
+    }
+    // Start a named tour when it gets its turn
+    {
+        let controller = controller.clone();
+        let named = registry.is_some();
+        use_effect_with(owns_controller, move |owns_controller| {
+            if named && *owns_controller {
+                controller.dispatch(TourAction::StartAt(start_step));
+            }
+        });
+    }
+    // Take the turn when a named tour is started while no tour is active
+    {
+        let registry = registry.clone();
+        let id = id.clone();
+        let unclaimed = controller.is_active()
+            && registry
+                .as_ref()
+                .is_some_and(|registry| registry.active().is_none());
+        use_effect_with(unclaimed, move |unclaimed| {
+            if let Some(registry) = registry.filter(|_| *unclaimed) {
+                registry.dispatch(TourRegistryAction::Claim(id));
+            }
+        });
+    }
+    // Hand the turn over to the next tour once the tour ends, whether it was completed,
+    // skipped or stopped. A tour losing its turn to another one stays queued.
+    {
+        let registry = registry.clone();
+        let id = id.clone();
+        let was_running = use_mut_ref(|| false);
+        let running = (owns_controller, controller.is_active());
+        use_effect_with(running, move |(owns_controller, active)| {
+            let ended = *was_running.borrow() && *owns_controller && !*active;
+            *was_running.borrow_mut() = *owns_controller && *active;
+            if let Some(registry) = registry.filter(|_| ended) {
+                registry.dispatch(TourRegistryAction::Finish(id));
+            }
+        });
     }
 
-    let on_next = {
-        let current_step = current_step.clone();
-        let step_count = config.steps.len();
-        let show_tour = show_tour.clone();
-        let on_close = on_close.clone();
-        Callback::from(move |_| {
-            if *current_step < step_count - 1 {
-                current_step.set(*current_step + 1);
+    let current = controller
+        .current_step()
+        .and_then(|index| steps.get(index).map(|step| (index, step)));
+
+    // Get the rectangle of the selected element, `None` for floating steps
+    let target = current
+        .and_then(|(_, step)| step.selector.clone())
+        .map(|selector| {
+            let rect = crate::models::get_element_rect(&selector).ok();
+            (selector, rect)
+        });
+    let missing_selector = target
+        .as_ref()
+        .filter(|(_, rect)| rect.is_none())
+        .map(|(selector, _)| selector.clone());
+
+    // Wait for a missing target which is rendered later, until the step timed out.
+    // Timeouts only count for the run and step they happened in.
+    let step_key = current.map(|(index, _)| (controller.runs(), index));
+    let timed_out_step = use_state_eq(|| None::<(usize, usize)>);
+    {
+        let timed_out_step = timed_out_step.clone();
+        use_effect_with(step_key, move |_| timed_out_step.set(None));
+    }
+    let wait_for_target = current
+        .filter(|_| missing_selector.is_some() && *timed_out_step != step_key)
+        .and_then(|(_, step)| step.wait_for_target);
+    let waiting = wait_for_target.is_some();
+    {
+        let force_update = use_force_update();
+        let timed_out_step = timed_out_step.clone();
+        let step_key = step_key.filter(|_| owns_controller);
+        let wait_for = wait_for_target.zip(missing_selector.clone());
+        use_effect_with((step_key, wait_for), move |(step_key, wait_for)| {
+            let waiter =
+                step_key
+                    .zip(wait_for.clone())
+                    .map(|(step_key, (timeout, selector))| {
+                        let on_timeout = timed_out_step.clone();
+                        crate::models::wait_for_element(&selector, timeout, move |result| {
+                            match result {
+                                Ok(_) => force_update.force_update(),
+                                Err(_) => on_timeout.set(Some(step_key)),
+                            }
+                        })
+                        .map_err(|_| timed_out_step.set(Some(step_key)))
+                    });
+            move || drop(waiter)
+        });
+    }
+    let missing_selector = missing_selector.filter(|_| !waiting);
+    let missing_target_policy = current
+        .and_then(|(_, step)| step.missing_target)
+        .unwrap_or(config.missing_target);
+    let step_target = match &target {
+        None => StepTarget::NoTarget,
+        Some((_, Some(_))) => StepTarget::Found,
+        Some(_) if waiting => StepTarget::Waiting,
+        Some(_) => StepTarget::Missing,
+    };
+    // Only steps the user actually sees are reported, remembered and have their hooks called.
+    // Steps taken over from another browser tab were already reported there.
+    let shown =
+        owns_controller && step_target.is_shown(missing_target_policy) && !controller.is_synced();
+
+    // Apply the missing target policy when the target of the current step can not be found
+    {
+        let controller = controller.clone();
+        let on_error = config.on_error.clone();
+        let on_event = config.on_event.clone();
+        let current_step = current
+            .map(|(index, _)| config_index(&step_indices, index))
+            .filter(|_| owns_controller);
+        use_effect_with(
+            (current_step, missing_selector.clone()),
+            move |(current_step, missing_selector)| {
+                let (Some(step), Some(selector)) = (*current_step, missing_selector.clone()) else {
+                    return;
+                };
+                if let Some(cb) = &on_event {
+                    cb.emit(TourEvent::TargetMissing {
+                        index: step,
+                        selector: selector.clone(),
+                    });
+                }
+                if let Some(cb) = &on_error {
+                    cb.emit(TourError::TargetNotFound { step, selector });
+                }
+                match missing_target_policy {
+                    MissingTargetPolicy::Skip => controller.dispatch(TourAction::SkipStep),
+                    MissingTargetPolicy::Floating => {}
+                    MissingTargetPolicy::End => controller.dispatch(TourAction::Stop),
+                }
+            },
+        );
+    }
+
+    // Scroll the selected element into view when the step changes
+    {
+        let found_selector = target
+            .as_ref()
+            .filter(|(_, rect)| rect.is_some() && owns_controller)
+            .map(|(selector, _)| selector.clone());
+        let current_step = current.map(|(index, _)| index);
+        use_effect_with((current_step, found_selector), |(_, found_selector)| {
+            if let Some(rect) = found_selector
+                .as_deref()
+                .and_then(|selector| crate::models::get_element_rect(selector).ok())
+            {
+                scroll_into_view(&rect);
+            }
+        });
+    }
+
+    // Re-measure and re-place the highlight and tooltip when the layout changes
+    {
+        let force_update = use_force_update();
+        let active = owns_controller && current.is_some();
+        let found_selector = target
+            .as_ref()
+            .filter(|(_, rect)| rect.is_some())
+            .map(|(selector, _)| selector.clone());
+        use_effect_with((active, found_selector), move |(active, found_selector)| {
+            let observer = active
+                .then(|| {
+                    LayoutObserver::new(found_selector.as_deref(), move || {
+                        force_update.force_update()
+                    })
+                    .ok()
+                })
+                .flatten();
+            move || drop(observer)
+        });
+    }
+
+    // Measure the rendered tooltip and place it again with its real size before it is painted.
+    // The height of the header, progress and navigation is kept apart from the content's.
+    let tooltip_ref = use_node_ref();
+    let tooltip_size = use_state_eq(|| (TOOLTIP_WIDTH, TOOLTIP_HEIGHT));
+    let chrome_height = use_state_eq(|| 0);
+    {
+        let tooltip_ref = tooltip_ref.clone();
+        let tooltip_size = tooltip_size.clone();
+        let chrome_height = chrome_height.clone();
+        use_effect(move || {
+            if let Some(tooltip) = tooltip_ref.cast::<web_sys::Element>() {
+                let rect = tooltip.get_bounding_client_rect();
+                let height = rect.height().round() as i32;
+                tooltip_size.set((rect.width().round() as i32, height));
+                if let Ok(Some(text)) = tooltip.query_selector(".introjs-tooltiptext") {
+                    let text_height = text.get_bounding_client_rect().height().round() as i32;
+                    chrome_height.set(height - text_height);
+                }
+            }
+        });
+    }
+
+    // Follow the tour when it is moved on or finished in another browser tab
+    #[cfg(feature = "storage")]
+    {
+        use crate::controller::TourState;
+
+        let controller = controller.clone();
+        let storage = storage.clone();
+        let config_steps = config.steps.clone();
+        let step_indices = step_indices.clone();
+        let active = owns_controller && controller.is_active();
+        use_effect_with((active, id.clone()), move |(active, id)| {
+            let id = id.clone();
+            let listener = storage.filter(|_| *active).and_then(|storage| {
+                crate::utils::listen_storage_changes(move |changed| {
+                    // Keys may be namespaced, only the record in the tour's own storage counts
+                    if storage.key_from_backend(&changed) != Some(TourRecord::key(&id)) {
+                        return;
+                    }
+                    let record = TourRecord::load(&storage, &id);
+                    let state = match record.status {
+                        TourStatus::Completed => TourState::Completed,
+                        TourStatus::Dismissed => TourState::Skipped,
+                        TourStatus::InProgress => match record
+                            .last_step_in(&config_steps)
+                            .and_then(|index| shown_index(&step_indices, index))
+                        {
+                            Some(step) => TourState::Active(step),
+                            None => return,
+                        },
+                        TourStatus::New => return,
+                    };
+                    controller.dispatch(TourAction::Sync(state));
+                })
+                .ok()
+            });
+            move || drop(listener)
+        });
+    }
+
+    // Pass the events recorded by the controller on, once per event
+    {
+        let has_context = context.is_some();
+        let seen_events = use_mut_ref(|| {
+            // Events of a shared controller from before this tour was mounted are not ours
+            if has_context {
+                controller.event_count()
             } else {
-                // Last step — treat as completion
-                show_tour.set(false);
-                if let Some(cb) = &on_close {
-                    cb.emit(());
+                0
+            }
+        });
+        // The first start of a resumed tour continues its previous run
+        let resuming = use_mut_ref(|| auto_start && resume_step.is_some());
+        let controller = controller.clone();
+        let on_event = config.on_event.clone();
+        let on_complete = config.on_complete.clone();
+        let storage = storage.clone();
+        let id = id.clone();
+        let version = config.version;
+        let clock = clock.clone();
+        let step_indices = step_indices.clone();
+        use_effect_with(controller.event_count(), move |event_count| {
+            if owns_controller {
+                for event in controller.events_since(*seen_events.borrow()) {
+                    if let Some(cb) = &on_event {
+                        cb.emit(
+                            event
+                                .clone()
+                                .map_steps(|index| config_index(&step_indices, index)),
+                        );
+                    }
+                    match event {
+                        TourEvent::Started if resuming.replace(false) => {
+                            update_record(&storage, &id, |record| record.resume(clock.now()))
+                        }
+                        TourEvent::Started => {
+                            update_record(&storage, &id, |record| record.start(clock.now()))
+                        }
+                        TourEvent::Completed => {
+                            update_record(&storage, &id, |record| {
+                                record.finish(TourStatus::Completed, version, clock.now())
+                            });
+                            if let Some(cb) = &on_complete {
+                                cb.emit(());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            *seen_events.borrow_mut() = *event_count;
+        });
+    }
+
+    // Report shown steps and remember them as the progress of the tour
+    {
+        let on_event = config.on_event.clone();
+        let storage = storage.clone();
+        let id = id.clone();
+        let clock = clock.clone();
+        let shown_step = current
+            .filter(|_| shown)
+            .map(|(index, step)| (config_index(&step_indices, index), step.id.clone()));
+        use_effect_with(shown_step, move |shown_step| {
+            if let Some((index, step_id)) = shown_step {
+                update_record(&storage, &id, |record| {
+                    record.show_step(*index, step_id.clone(), clock.now())
+                });
+                if let Some(cb) = &on_event {
+                    cb.emit(TourEvent::StepShown {
+                        index: *index,
+                        id: step_id.clone(),
+                    });
+                }
+            }
+        });
+    }
+
+    // Call the lifecycle hooks of steps when they are shown and left
+    {
+        let hooked_step = current.filter(|_| shown).map(|(index, step)| {
+            (
+                config_index(&step_indices, index),
+                step.on_enter.clone(),
+                step.on_leave.clone(),
+            )
+        });
+        use_effect_with(
+            hooked_step.as_ref().map(|(index, _, _)| *index),
+            move |_| {
+                if let Some((index, Some(cb), _)) = &hooked_step {
+                    cb.emit(*index);
+                }
+                move || {
+                    if let Some((index, _, Some(cb))) = hooked_step {
+                        cb.emit(index);
+                    }
                 }
+            },
+        );
+    }
+
+    if !owns_controller {
+        return html! {};
+    }
+    let Some((current_step, step)) = current else {
+        return html! {};
+    };
+    if missing_selector.is_some() && missing_target_policy != MissingTargetPolicy::Floating {
+        return html! {};
+    }
+
+    // Move to another step, or complete the tour with `None`, unless the current step vetoes it
+    let navigate = {
+        let controller = controller.clone();
+        let before_change = step.before_change.clone();
+        let step_indices = step_indices.clone();
+        Callback::from(move |to: Option<usize>| {
+            let proceed = {
+                let controller = controller.clone();
+                Callback::from(move |_| {
+                    controller.dispatch(TourAction::Transition {
+                        from: current_step,
+                        to,
+                    })
+                })
+            };
+            match &before_change {
+                Some(before_change) => before_change.emit(StepTransition::new(
+                    config_index(&step_indices, current_step),
+                    to.map(|to| config_index(&step_indices, to)),
+                    proceed,
+                )),
+                None => proceed.emit(()),
             }
         })
     };
 
+    let on_next = {
+        let navigate = navigate.clone();
+        let next_step = Some(current_step + 1).filter(|step| *step < step_count);
+        Callback::from(move |_| navigate.emit(next_step))
+    };
+
     let on_prev = {
-        let current_step = current_step.clone();
+        let navigate = navigate.clone();
         Callback::from(move |_| {
-            if *current_step > 0 {
-                current_step.set(*current_step - 1);
+            if current_step > 0 {
+                navigate.emit(Some(current_step - 1));
             }
         })
     };
 
     let on_skip = {
-        let show_tour = show_tour.clone();
+        let controller = controller.clone();
+        let on_dismiss = config.on_dismiss.clone();
+        let storage = storage.clone();
         let id = id.clone();
-        let on_close = on_close.clone();
+        let version = config.version;
+        let clock = clock.clone();
+        let step_indices = step_indices.clone();
         Callback::from(move |_| {
-            show_tour.set(false);
-            #[cfg(feature = "storage")]
-            let _ = LocalStorage::set(format!("{}-show", id), false);
-            if let Some(cb) = &on_close {
-                cb.emit(());
+            controller.dispatch(TourAction::Skip);
+            update_record(&storage, &id, |record| {
+                record.finish(TourStatus::Dismissed, version, clock.now())
+            });
+            if let Some(cb) = &on_dismiss {
+                cb.emit(config_index(&step_indices, current_step));
             }
         })
     };
 
-    let on_progress_click = {
-        let current_step = current_step.clone();
-        Callback::from(move |step: usize| {
-            current_step.set(step);
-        })
-    };
-
-    let selector: String = config.steps[*current_step].selector.clone();
+    let on_progress_click = Callback::from(move |step: usize| {
+        if step != current_step {
+            navigate.emit(Some(step));
+        }
+    });
 
-    // Get the rectangle of the selected element
-    let selector_rect = crate::models::get_element_rect(&selector).unwrap_or_default();
+    let (tooltip_width, tooltip_height) = *tooltip_size;
+    let (selector_rect, tooltip_left, tooltip_top, position) =
+        match target.and_then(|(_, rect)| rect) {
+            Some(selector_rect) => {
+                // Calculate the tooltip position
+                let position = compute_position(
+                    &selector_rect,
+                    step.placement,
+                    step.alignment,
+                    (tooltip_width, tooltip_height),
+                    &viewport(),
+                    &config.middleware,
+                );
 
-    // Scroll the selected element into view
-    scroll_into_view(&selector_rect);
+                // Adjust tooltip position relative to the selected element
+                (
+                    selector_rect,
+                    position.x - selector_rect.left(),
+                    position.y - selector_rect.top(),
+                    Some(position),
+                )
+            }
+            // Floating steps and steps without target are centered in the viewport, without arrow
+            None => (
+                viewport_center(),
+                -tooltip_width / 2,
+                -tooltip_height / 2,
+                None,
+            ),
+        };
 
-    // Calculate the tooltip position
-    let (arrow_position, dx, dy) = calculate_arrow_position(
-        &selector_rect,
-        TOOLTIP_WIDTH,
-        TOOLTIP_HEIGHT,
-        window_width(),
-        window_height(),
-    );
+    let (max_width, max_height) = max_size(step, config, position.as_ref(), *chrome_height);
 
-    // Adjust tooltip position relative to the selected element
-    let tooltip_left = dx - selector_rect.left();
-    let tooltip_top = dy - selector_rect.top();
+    // Show a loading state while waiting for the target
+    let content = if waiting {
+        html! { <Loading /> }
+    } else {
+        html! { <Content content={step.content.clone()} max_height={max_height} /> }
+    };
 
     html! {
         <div class="tour" id={id.clone()}>
             <div class="introjsFloatingElement"></div>
             <div class="introjs-overlay" style="inset: 0px; position: fixed; cursor: pointer;" onclick={on_skip.clone()}></div>
-            <Selection rect={selector_rect} />
+            // Floating steps have nothing to highlight
+            if position.is_some() {
+                <Selection rect={selector_rect} />
+            }
             <div class="introjs-tooltipReferenceLayer"
                 style={format!("left: {}px; top: {}px; width: {}px; height: {}px; position: absolute;",
                     selector_rect.left(), selector_rect.top(), selector_rect.width, selector_rect.height)} >
-                <div class="introjs-tooltip" role="dialog"
-                    style={format!("left: {}px; top: {}px; position: absolute; width: {}px; height: {}px;",
-                        tooltip_left, tooltip_top, TOOLTIP_WIDTH, TOOLTIP_HEIGHT)}>
-                    <div class={format!("introjs-arrow {}", arrow_position)} style="display: inherit;"></div>
+                // Without the intro.js position classes, whose `!important` offsets would override the calculated ones
+                <div ref={tooltip_ref} class={classes!("introjs-tooltip", position.map(|position| position.tooltip_class()).unwrap_or("tour-tooltip-floating"))} role="dialog"
+                    style={format!("left: {}px; top: {}px; position: absolute; width: max-content; max-width: {}px;",
+                        tooltip_left, tooltip_top, max_width)}>
+                    if let Some(position) = position {
+                        <div class={format!("introjs-arrow {}", position.arrow_class())} style={arrow_style(&position)}></div>
+                    }
                     <div class="introjs-tooltip-header">
-                        <StepInfo value={*current_step} />
+                        <StepInfo value={current_step} />
                         <a class="introjs-skipbutton" href="#" onclick={on_skip.clone()}>
                             {"×"}
                         </a>
                     </div>
-                    <Content content={config.steps[*current_step].content.clone()} />
-                    <Progress current={*current_step} total={config.steps.len()} on_click={on_progress_click} />
+                    {content}
+                    <Progress current={current_step} total={step_count} on_click={on_progress_click} />
                     <Navigation on_prev={on_prev} on_next={on_next} />
                     <div class="introjs-tooltipfooter"></div>
                 </div>
@@ -153,3 +755,59 @@ pub fn tour(config: &TourConfig) -> Html {
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Alignment;
+
+    #[test]
+    fn test_max_size() {
+        let mut step = TourStep::new("#target", "Content");
+        let mut config = TourConfig::default();
+        assert_eq!(
+            max_size(&step, &config, None, 80),
+            (TOOLTIP_WIDTH as u32, None)
+        );
+
+        config.max_width = Some(400);
+        config.max_height = Some(300);
+        assert_eq!(max_size(&step, &config, None, 80), (400, Some(300)));
+
+        step.max_width = Some(500);
+        step.max_height = Some(150);
+        assert_eq!(max_size(&step, &config, None, 80), (500, Some(150)));
+
+        // The size middleware limits the whole tooltip, 80px of which are not content
+        let mut position = TooltipPosition {
+            placement: Placement::Bottom,
+            alignment: Alignment::Center,
+            x: 0,
+            y: 0,
+            arrow_offset: None,
+            max_width: Some(450),
+            max_height: Some(250),
+        };
+        assert_eq!(
+            max_size(&step, &config, Some(&position), 80),
+            (450, Some(150))
+        );
+
+        step.max_height = None;
+        config.max_height = None;
+        assert_eq!(
+            max_size(&step, &config, Some(&position), 80),
+            (450, Some(170))
+        );
+
+        position.max_height = Some(50);
+        assert_eq!(
+            max_size(&step, &config, Some(&position), 80),
+            (450, Some(0))
+        );
+
+        position.max_width = None;
+        position.max_height = None;
+        assert_eq!(max_size(&step, &config, Some(&position), 80), (500, None));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::models::get_element;
+use gloo_events::{EventListener, EventListenerOptions};
+use std::cell::Cell;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
+use web_sys::{ResizeObserver, Window};
+
+type ResizeCallback = Closure<dyn FnMut(wasm_bindgen::JsValue, ResizeObserver)>;
+
+/// Watches for layout changes which move the highlighted element.
+///
+/// The callback is called when the window is resized or scrolled, including scrolling
+/// of nested containers, and when the size of the target element changes. It is called
+/// at most once per animation frame, however many changes happened during the frame.
+/// All subscriptions end when the observer is dropped.
+pub struct LayoutObserver {
+    _listeners: Vec<EventListener>,
+    resize_observer: Option<(ResizeObserver, ResizeCallback)>,
+    frame: Rc<AnimationFrame>,
+}
+
+// Runs a callback in the next animation frame, once for all requests made until then
+struct AnimationFrame {
+    window: Window,
+    pending: Cell<Option<i32>>,
+    on_frame: Closure<dyn FnMut()>,
+}
+
+impl AnimationFrame {
+    fn new(window: Window, callback: impl Fn() + 'static) -> Rc<Self> {
+        Rc::new_cyclic(|frame: &Weak<Self>| {
+            let frame = frame.clone();
+            AnimationFrame {
+                window,
+                pending: Cell::new(None),
+                on_frame: Closure::new(move || {
+                    if let Some(frame) = frame.upgrade() {
+                        frame.pending.set(None);
+                    }
+                    callback();
+                }),
+            }
+        })
+    }
+
+    fn request(&self) {
+        if self.pending.get().is_none()
+            && let Ok(handle) = self
+                .window
+                .request_animation_frame(self.on_frame.as_ref().unchecked_ref())
+        {
+            self.pending.set(Some(handle));
+        }
+    }
+
+    fn cancel(&self) {
+        if let Some(handle) = self.pending.take() {
+            let _ = self.window.cancel_animation_frame(handle);
+        }
+    }
+}
+
+impl LayoutObserver {
+    /// Starts watching the window and, if given, the element matching `selector`.
+    ///
+    /// The window is watched even if no element matches `selector` yet.
+    pub fn new(selector: Option<&str>, callback: impl Fn() + 'static) -> Result<Self, String> {
+        let window = web_sys::window().ok_or_else(|| "Failed to get window".to_string())?;
+        let frame = AnimationFrame::new(window.clone(), callback);
+
+        let listeners = vec![
+            {
+                let frame = frame.clone();
+                EventListener::new(&window, "resize", move |_| frame.request())
+            },
+            {
+                let frame = frame.clone();
+                EventListener::new_with_options(
+                    &window,
+                    "scroll",
+                    EventListenerOptions::run_in_capture_phase(),
+                    move |_| frame.request(),
+                )
+            },
+        ];
+
+        let resize_observer = selector
+            .and_then(|selector| get_element(selector).ok())
+            .map(|element| {
+                let frame = frame.clone();
+                let on_resize: ResizeCallback = Closure::new(move |_, _| frame.request());
+                let observer =
+                    ResizeObserver::new(on_resize.as_ref().unchecked_ref()).map_err(|e| {
+                        e.as_string()
+                            .unwrap_or_else(|| "Failed to create ResizeObserver".to_string())
+                    })?;
+                observer.observe(&element);
+                Ok::<_, String>((observer, on_resize))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            _listeners: listeners,
+            resize_observer,
+            frame,
+        })
+    }
+}
+
+impl Drop for LayoutObserver {
+    fn drop(&mut self) {
+        if let Some((observer, _)) = &self.resize_observer {
+            observer.disconnect();
+        }
+        self.frame.cancel();
+    }
+}
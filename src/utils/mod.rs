@@ -1,5 +1,9 @@
+mod layout_observer;
 mod position;
-mod relative_position;
+#[cfg(feature = "storage")]
+mod storage_events;
 
+pub use layout_observer::*;
 pub use position::*;
-pub use relative_position::*;
+#[cfg(feature = "storage")]
+pub use storage_events::*;
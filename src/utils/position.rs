@@ -1,169 +1,294 @@
-use crate::models::Rect;
-use crate::tour::ARROW_SIZE;
+use crate::models::{Alignment, Middleware, Placement, Rect};
 
-/// Calculates the best position for the tooltip relative to the selected element.
+/// The position of a tooltip next to its target element.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TooltipPosition {
+    /// The side of the target the tooltip is placed on, never `Placement::Auto`.
+    pub placement: Placement,
+
+    /// How the tooltip is aligned along the edge of the target.
+    pub alignment: Alignment,
+
+    /// The x-coordinate of the tooltip.
+    pub x: i32,
+
+    /// The y-coordinate of the tooltip.
+    pub y: i32,
+
+    /// The offset of the arrow's center along the edge of the tooltip facing the target,
+    /// from the left for tooltips above or below and from the top for tooltips beside the
+    /// target. The arrow points at the center of the target, also when the tooltip was
+    /// moved to stay within the window, and stays within the tooltip.
+    ///
+    /// `None` without `Middleware::Arrow`, leaving the arrow where intro.js puts it.
+    pub arrow_offset: Option<i32>,
+
+    /// The maximum width of the tooltip set by `Middleware::Size`.
+    pub max_width: Option<i32>,
+
+    /// The maximum height of the tooltip set by `Middleware::Size`.
+    pub max_height: Option<i32>,
+}
+
+impl TooltipPosition {
+    /// Returns the position class of the tooltip, e.g. `"tour-tooltip-bottom-start"`, for
+    /// styling it depending on its side and alignment.
+    ///
+    /// These are not the intro.js position classes like `introjs-bottom-left-aligned`,
+    /// which come with `!important` offsets of their own overriding the calculated position.
+    pub fn tooltip_class(&self) -> &'static str {
+        match (self.placement, self.alignment) {
+            (Placement::Top, Alignment::Start) => "tour-tooltip-top-start",
+            (Placement::Top, Alignment::Center) => "tour-tooltip-top-center",
+            (Placement::Top, Alignment::End) => "tour-tooltip-top-end",
+            (Placement::Left, Alignment::Start) => "tour-tooltip-left-start",
+            (Placement::Left, Alignment::Center) => "tour-tooltip-left-center",
+            (Placement::Left, Alignment::End) => "tour-tooltip-left-end",
+            (Placement::Right, Alignment::Start) => "tour-tooltip-right-start",
+            (Placement::Right, Alignment::Center) => "tour-tooltip-right-center",
+            (Placement::Right, Alignment::End) => "tour-tooltip-right-end",
+            (_, Alignment::Start) => "tour-tooltip-bottom-start",
+            (_, Alignment::Center) => "tour-tooltip-bottom-center",
+            (_, Alignment::End) => "tour-tooltip-bottom-end",
+        }
+    }
+
+    /// Returns the intro.js class of the tooltip's arrow, which points at the target
+    /// from the opposite side, e.g. `"top-middle"` for a centered tooltip below the target.
+    pub fn arrow_class(&self) -> &'static str {
+        match (self.placement, self.alignment) {
+            (Placement::Top, Alignment::Start) => "bottom",
+            (Placement::Top, Alignment::Center) => "bottom-middle",
+            (Placement::Top, Alignment::End) => "bottom-right",
+            (Placement::Left, Alignment::End) => "right-bottom",
+            (Placement::Left, _) => "right",
+            (Placement::Right, Alignment::End) => "left-bottom",
+            (Placement::Right, _) => "left",
+            (_, Alignment::Start) => "top",
+            (_, Alignment::Center) => "top-middle",
+            (_, Alignment::End) => "top-right",
+        }
+    }
+}
+
+/// Calculates the position of the tooltip relative to the selected element by running
+/// the given middleware in order.
 ///
-/// This function determines the optimal position (top, bottom, left, right) for the tooltip,
-/// ensuring it stays within the window boundaries and chooses the side with the most available space.
+/// The tooltip starts out on the preferred side, aligned as given and touching the
+/// element. `Placement::Auto` is resolved first to the side with the most available
+/// space the tooltip fits on, taking all `Middleware::Offset` into account. Each
+/// middleware then adjusts the position:
 ///
-/// # Parameters
+/// - `Offset` moves the tooltip away from the element.
+/// - `Flip` moves it to the first side of `Placement::fallbacks` it fits on with the
+///   offsets so far, or back to the preferred side.
+/// - `Shift` moves it into the viewport.
+/// - `Size` shrinks it to the space available on its side and sets the maximum size.
+/// - `Arrow` sets the offset of the arrow.
 ///
-/// - `selected_rect`: The rectangle representing the selected element.
-/// - `tooltip_width`: The width of the tooltip.
-/// - `tooltip_height`: The height of the tooltip.
-/// - `window_width`: The width of the browser window.
-/// - `window_height`: The height of the browser window.
+/// `Offset`, `Flip` and `Size` place the tooltip next to the element again, so they
+/// should run before `Shift`.
 ///
-/// # Returns
+/// # Parameters
 ///
-/// A tuple containing:
-/// - `arrow_position`: A string indicating where the arrow should point (e.g., "top", "bottom").
-/// - `x`: The x-coordinate for positioning the tooltip.
-/// - `y`: The y-coordinate for positioning the tooltip.
-pub fn calculate_arrow_position(
+/// - `selected_rect`: The rectangle representing the selected element.
+/// - `placement`: The preferred side of the selected element.
+/// - `alignment`: The alignment of the tooltip along the edge of the selected element.
+/// - `tooltip_size`: The width and height of the tooltip.
+/// - `viewport`: The visible part of the page, in the same document coordinates as the
+///   selected element, i.e. the window size at the scroll offsets.
+/// - `middleware`: The middleware to run.
+pub fn compute_position(
     selected_rect: &Rect,
-    tooltip_width: i32,
-    tooltip_height: i32,
-    window_width: i32,
-    window_height: i32,
-) -> (&'static str, i32, i32) {
-    let top_space = selected_rect.top();
-    let bottom_space = window_height - selected_rect.bottom();
-    let left_space = selected_rect.left();
-    let right_space = window_width - selected_rect.right();
-
-    // Determine if the tooltip can fit on each side
-    let can_place_top = top_space >= tooltip_height + ARROW_SIZE;
-    let can_place_bottom = bottom_space >= tooltip_height + ARROW_SIZE;
-    let can_place_left = left_space >= tooltip_width + ARROW_SIZE;
-    let can_place_right = right_space >= tooltip_width + ARROW_SIZE;
-
-    // Define preferred order of positions
-    let preferred_order = ["bottom", "top", "left", "right"];
-
-    // Create a list of possible positions where the tooltip fits
-    let mut possible_positions = Vec::new();
-
-    if can_place_bottom {
-        possible_positions.push((
-            "top",
-            bottom_space,
-            0, // Index for ordering
-            selected_rect.left() + (selected_rect.width - tooltip_width) / 2,
-            selected_rect.bottom() + ARROW_SIZE,
-        ));
-    }
-    if can_place_top {
-        possible_positions.push((
-            "bottom",
-            top_space,
-            1,
-            selected_rect.left() + (selected_rect.width - tooltip_width) / 2,
-            selected_rect.top() - tooltip_height - ARROW_SIZE,
-        ));
-    }
-    if can_place_right {
-        possible_positions.push((
-            "left",
-            right_space,
-            2,
-            selected_rect.right() + ARROW_SIZE,
-            selected_rect.top() + (selected_rect.height - tooltip_height) / 2,
-        ));
-    }
-    if can_place_left {
-        possible_positions.push((
-            "right",
-            left_space,
-            3,
-            selected_rect.left() - tooltip_width - ARROW_SIZE,
-            selected_rect.top() + (selected_rect.height - tooltip_height) / 2,
-        ));
-    }
-
-    // Choose the position with the most space, breaking ties by preferred order (lower index wins)
-    let (arrow_position, _, _, mut x_pos, mut y_pos) = if let Some(position) = possible_positions
-        .into_iter()
-        .max_by(|a, b| {
-            let space_cmp = a.1.cmp(&b.1);
-            if space_cmp == std::cmp::Ordering::Equal {
-                b.2.cmp(&a.2)
-            } else {
-                space_cmp
-            }
-        })
-    {
-        position
-    } else {
-        // Default to placing below the element
-        (
-            "top",
-            bottom_space,
-            0,
-            selected_rect.left() + (selected_rect.width - tooltip_width) / 2,
-            selected_rect.bottom() + ARROW_SIZE,
-        )
+    placement: Placement,
+    alignment: Alignment,
+    tooltip_size: (i32, i32),
+    viewport: &Rect,
+    middleware: &[Middleware],
+) -> TooltipPosition {
+    let space = |side: Placement| match side {
+        Placement::Top => selected_rect.top() - viewport.top(),
+        Placement::Bottom | Placement::Auto => viewport.bottom() - selected_rect.bottom(),
+        Placement::Left => selected_rect.left() - viewport.left(),
+        Placement::Right => viewport.right() - selected_rect.right(),
     };
 
-    // Adjust position to keep the tooltip within window boundaries
-    if x_pos < 0 {
-        x_pos = 0;
-    } else if x_pos + tooltip_width > window_width {
-        x_pos = window_width - tooltip_width;
-    }
+    // Determine if the tooltip can fit on a side with the given gap
+    let fits = |side: Placement, (width, height): (i32, i32), gap: i32| match side {
+        Placement::Left | Placement::Right => space(side) >= width + gap,
+        _ => space(side) >= height + gap,
+    };
+
+    let place = |side: Placement, (width, height): (i32, i32), gap: i32| {
+        let aligned_x = match alignment {
+            Alignment::Start => selected_rect.left(),
+            Alignment::Center => selected_rect.left() + (selected_rect.width - width) / 2,
+            Alignment::End => selected_rect.right() - width,
+        };
+        let aligned_y = match alignment {
+            Alignment::Start => selected_rect.top(),
+            Alignment::Center => selected_rect.top() + (selected_rect.height - height) / 2,
+            Alignment::End => selected_rect.bottom() - height,
+        };
+        match side {
+            Placement::Top => (aligned_x, selected_rect.top() - height - gap),
+            Placement::Bottom | Placement::Auto => (aligned_x, selected_rect.bottom() + gap),
+            Placement::Left => (selected_rect.left() - width - gap, aligned_y),
+            Placement::Right => (selected_rect.right() + gap, aligned_y),
+        }
+    };
+
+    let preferred = match placement {
+        // Choose the side with the most space, breaking ties by the order of the sides
+        Placement::Auto => {
+            let gap = middleware
+                .iter()
+                .map(|middleware| match middleware {
+                    Middleware::Offset(offset) => *offset,
+                    _ => 0,
+                })
+                .sum();
+            [
+                Placement::Bottom,
+                Placement::Top,
+                Placement::Right,
+                Placement::Left,
+            ]
+            .into_iter()
+            .filter(|side| fits(*side, tooltip_size, gap))
+            .rev()
+            .max_by_key(|side| space(*side))
+            .unwrap_or(Placement::Bottom)
+        }
+        preferred => preferred,
+    };
+
+    let mut side = preferred;
+    let mut size = tooltip_size;
+    let mut gap = 0;
+    let (mut x_pos, mut y_pos) = place(side, size, gap);
+    let mut position = TooltipPosition {
+        placement: side,
+        alignment,
+        x: x_pos,
+        y: y_pos,
+        arrow_offset: None,
+        max_width: None,
+        max_height: None,
+    };
+
+    for middleware in middleware {
+        match *middleware {
+            Middleware::Offset(offset) => {
+                gap += offset;
+                (x_pos, y_pos) = place(side, size, gap);
+            }
+            Middleware::Flip => {
+                side = preferred
+                    .fallbacks()
+                    .into_iter()
+                    .find(|side| fits(*side, size, gap))
+                    .unwrap_or(preferred);
+                (x_pos, y_pos) = place(side, size, gap);
+            }
+            Middleware::Shift { padding } => {
+                // Adjust position to keep the tooltip within the viewport
+                if x_pos < viewport.left() + padding {
+                    x_pos = viewport.left() + padding;
+                } else if x_pos + size.0 > viewport.right() - padding {
+                    x_pos = viewport.right() - size.0 - padding;
+                }
 
-    if y_pos < 0 {
-        y_pos = 0;
-    } else if y_pos + tooltip_height > window_height {
-        y_pos = window_height - tooltip_height;
+                if y_pos < viewport.top() + padding {
+                    y_pos = viewport.top() + padding;
+                } else if y_pos + size.1 > viewport.bottom() - padding {
+                    y_pos = viewport.bottom() - size.1 - padding;
+                }
+            }
+            Middleware::Size { padding } => {
+                let (max_width, max_height) = match side {
+                    Placement::Left | Placement::Right => {
+                        (space(side) - gap - padding, viewport.height - 2 * padding)
+                    }
+                    _ => (viewport.width - 2 * padding, space(side) - gap - padding),
+                };
+                let (max_width, max_height) = (max_width.max(0), max_height.max(0));
+                position.max_width = Some(max_width);
+                position.max_height = Some(max_height);
+                size = (size.0.min(max_width), size.1.min(max_height));
+                (x_pos, y_pos) = place(side, size, gap);
+            }
+            Middleware::Arrow { padding } => {
+                // Aim the arrow at the center of the element, along the edge facing it
+                position.arrow_offset = Some(match side {
+                    Placement::Left | Placement::Right => {
+                        (selected_rect.top() + selected_rect.height / 2 - y_pos)
+                            .clamp(padding, (size.1 - padding).max(padding))
+                    }
+                    _ => (selected_rect.left() + selected_rect.width / 2 - x_pos)
+                        .clamp(padding, (size.0 - padding).max(padding)),
+                });
+            }
+        }
     }
 
-    (arrow_position, x_pos, y_pos)
+    position.placement = side;
+    position.x = x_pos;
+    position.y = y_pos;
+    position
 }
 
-
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::models::ARROW_SIZE;
     use rstest::rstest;
 
     const TOOLTIP_WIDTH: i32 = 100;
     const TOOLTIP_HEIGHT: i32 = 100;
 
-    const DEFAULT_WINDOW_WIDTH: i32 = 800;
-    const DEFAULT_WINDOW_HEIGHT: i32 = 600;
-
-    const ARROW_SIZE: i32 = 10;
+    const VIEWPORT: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
 
     #[rstest]
-    #[case("Element at top", Rect { x: 200, y: 20, width: 100, height: 50 }, "top", 200, 80)]
-    #[case("Element at bottom", Rect { x: 200, y: 500, width: 100, height: 50 }, "bottom", 200, 390)]
-    #[case("Element at left edge", Rect { x: 0, y: 200, width: 100, height: 50 }, "left", 110, 175)]
-    #[case("Element at right edge", Rect { x: 700, y: 200, width: 100, height: 50 }, "right", 590, 175)]
+    #[case("Element at top", Rect { x: 200, y: 20, width: 100, height: 50 }, Placement::Bottom, 200, 80)]
+    #[case("Element at bottom", Rect { x: 200, y: 500, width: 100, height: 50 }, Placement::Top, 200, 390)]
+    #[case("Element at left edge", Rect { x: 0, y: 200, width: 100, height: 50 }, Placement::Right, 110, 175)]
+    #[case("Element at right edge", Rect { x: 700, y: 200, width: 100, height: 50 }, Placement::Left, 590, 175)]
     fn test_calculate_arrow_position(
         #[case] name: &str,
         #[case] rect: Rect,
-        #[case] expected_arrow: &str,
+        #[case] expected_placement: Placement,
         #[case] expected_dx: i32,
         #[case] expected_dy: i32,
     ) {
-        let (arrow_position, dx, dy) = calculate_arrow_position(
+        let TooltipPosition {
+            placement,
+            x: dx,
+            y: dy,
+            ..
+        } = compute_position(
             &rect,
-            TOOLTIP_WIDTH,
-            TOOLTIP_HEIGHT,
-            DEFAULT_WINDOW_WIDTH,
-            DEFAULT_WINDOW_HEIGHT,
+            Placement::Auto,
+            Alignment::Center,
+            (TOOLTIP_WIDTH, TOOLTIP_HEIGHT),
+            &VIEWPORT,
+            &Middleware::defaults(),
         );
 
         // Add debug output
         println!(
-            "Test '{}': Expected arrow_position='{}', dx={}, dy={}. Got arrow_position='{}', dx={}, dy={}",
-            name, expected_arrow, expected_dx, expected_dy, arrow_position, dx, dy
+            "Test '{}': Expected placement={:?}, dx={}, dy={}. Got placement={:?}, dx={}, dy={}",
+            name, expected_placement, expected_dx, expected_dy, placement, dx, dy
         );
 
         assert_eq!(
-            arrow_position, expected_arrow,
-            "Arrow position mismatch for {}",
+            placement, expected_placement,
+            "Placement mismatch for {}",
             name
         );
         assert_eq!(dx, expected_dx, "DX mismatch for {}", name);
@@ -171,28 +296,34 @@ mod test {
     }
 
     #[rstest]
-    #[case("Small tooltip", Rect { x: 200, y: 200, width: 100, height: 50 }, 200, 100, "left", 310, 175)]
-    #[case("Element larger than tooltip", Rect { x: 200, y: 200, width: 400, height: 50 }, TOOLTIP_WIDTH, TOOLTIP_HEIGHT, "top", 350, 260)]
+    #[case("Small tooltip", Rect { x: 200, y: 200, width: 100, height: 50 }, 200, 100, Placement::Right, 310, 175)]
+    #[case("Element larger than tooltip", Rect { x: 200, y: 200, width: 400, height: 50 }, TOOLTIP_WIDTH, TOOLTIP_HEIGHT, Placement::Bottom, 350, 260)]
     fn test_calculate_arrow_position_with_different_sizes(
         #[case] name: &str,
         #[case] rect: Rect,
         #[case] tooltip_width: i32,
         #[case] tooltip_height: i32,
-        #[case] expected_arrow: &str,
+        #[case] expected_placement: Placement,
         #[case] expected_dx: i32,
         #[case] expected_dy: i32,
     ) {
-        let (arrow_position, dx, dy) = calculate_arrow_position(
+        let TooltipPosition {
+            placement,
+            x: dx,
+            y: dy,
+            ..
+        } = compute_position(
             &rect,
-            tooltip_width,
-            tooltip_height,
-            DEFAULT_WINDOW_WIDTH,
-            DEFAULT_WINDOW_HEIGHT,
+            Placement::Auto,
+            Alignment::Center,
+            (tooltip_width, tooltip_height),
+            &VIEWPORT,
+            &Middleware::defaults(),
         );
 
         assert_eq!(
-            arrow_position, expected_arrow,
-            "Arrow position mismatch for {}",
+            placement, expected_placement,
+            "Placement mismatch for {}",
             name
         );
         assert_eq!(dx, expected_dx, "DX mismatch for {}", name);
@@ -200,30 +331,332 @@ mod test {
     }
 
     #[rstest]
-    #[case("Element at (0, 0)", Rect { x: 0, y: 0, width: 50, height: 50 }, "left", 60, 0)]
-    #[case("Element at bottom right corner", Rect { x: 750, y: 550, width: 50, height: 50 }, "right", 640, 500)]
-    #[case("Element larger than window", Rect { x: -100, y: -100, width: 1000, height: 1000 }, "top", 350, 500)]
+    #[case("Element at (0, 0)", Rect { x: 0, y: 0, width: 50, height: 50 }, Placement::Right, 60, 0, 25)]
+    #[case("Element at bottom right corner", Rect { x: 750, y: 550, width: 50, height: 50 }, Placement::Left, 640, 500, 75)]
+    #[case("Element larger than window", Rect { x: -100, y: -100, width: 1000, height: 1000 }, Placement::Bottom, 350, 500, 50)]
     fn test_edge_cases(
         #[case] name: &str,
         #[case] rect: Rect,
-        #[case] expected_arrow: &str,
+        #[case] expected_placement: Placement,
         #[case] expected_dx: i32,
         #[case] expected_dy: i32,
+        #[case] expected_arrow_offset: i32,
     ) {
-        let (arrow_position, dx, dy) = calculate_arrow_position(
+        let position = compute_position(
             &rect,
-            TOOLTIP_WIDTH,
-            TOOLTIP_HEIGHT,
-            DEFAULT_WINDOW_WIDTH,
-            DEFAULT_WINDOW_HEIGHT,
+            Placement::Auto,
+            Alignment::Center,
+            (TOOLTIP_WIDTH, TOOLTIP_HEIGHT),
+            &VIEWPORT,
+            &Middleware::defaults(),
         );
 
         assert_eq!(
-            arrow_position, expected_arrow,
-            "Arrow position mismatch for {}",
+            position.placement, expected_placement,
+            "Placement mismatch for {}",
             name
         );
-        assert_eq!(dx, expected_dx, "DX mismatch for {}", name);
-        assert_eq!(dy, expected_dy, "DY mismatch for {}", name);
+        assert_eq!(position.x, expected_dx, "DX mismatch for {}", name);
+        assert_eq!(position.y, expected_dy, "DY mismatch for {}", name);
+        assert_eq!(
+            position.arrow_offset,
+            Some(expected_arrow_offset),
+            "Arrow offset mismatch for {}",
+            name
+        );
+    }
+
+    #[rstest]
+    #[case::fits(Placement::Top, Rect { x: 350, y: 250, width: 100, height: 50 }, Placement::Top, 350, 140)]
+    #[case::opposite(Placement::Top, Rect { x: 350, y: 50, width: 100, height: 50 }, Placement::Bottom, 350, 110)]
+    #[case::perpendicular(Placement::Top, Rect { x: 350, y: 50, width: 100, height: 500 }, Placement::Right, 460, 250)]
+    #[case::left(Placement::Left, Rect { x: 350, y: 250, width: 100, height: 50 }, Placement::Left, 240, 225)]
+    #[case::left_opposite(Placement::Left, Rect { x: 50, y: 250, width: 100, height: 50 }, Placement::Right, 160, 225)]
+    #[case::fits_nowhere(Placement::Right, Rect { x: -100, y: -100, width: 1000, height: 1000 }, Placement::Right, 700, 350)]
+    fn test_preferred_placement(
+        #[case] preferred: Placement,
+        #[case] rect: Rect,
+        #[case] expected_placement: Placement,
+        #[case] expected_dx: i32,
+        #[case] expected_dy: i32,
+    ) {
+        let position = compute_position(
+            &rect,
+            preferred,
+            Alignment::Center,
+            (TOOLTIP_WIDTH, TOOLTIP_HEIGHT),
+            &VIEWPORT,
+            &Middleware::defaults(),
+        );
+        assert_eq!(
+            position,
+            TooltipPosition {
+                placement: expected_placement,
+                alignment: Alignment::Center,
+                x: expected_dx,
+                y: expected_dy,
+                arrow_offset: Some(50),
+                max_width: None,
+                max_height: None,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::bottom_start(Placement::Bottom, Alignment::Start, 100, 260)]
+    #[case::bottom_end(Placement::Bottom, Alignment::End, 400, 260)]
+    #[case::right_start(Placement::Right, Alignment::Start, 510, 200)]
+    #[case::right_end(Placement::Right, Alignment::End, 510, 150)]
+    #[case::flipped_keeps_alignment(Placement::Left, Alignment::End, 510, 150)]
+    fn test_alignment(
+        #[case] placement: Placement,
+        #[case] alignment: Alignment,
+        #[case] expected_dx: i32,
+        #[case] expected_dy: i32,
+    ) {
+        let rect = Rect {
+            x: 100,
+            y: 200,
+            width: 400,
+            height: 50,
+        };
+        let position = compute_position(
+            &rect,
+            placement,
+            alignment,
+            (TOOLTIP_WIDTH, TOOLTIP_HEIGHT),
+            &VIEWPORT,
+            &Middleware::defaults(),
+        );
+        assert_eq!((position.x, position.y), (expected_dx, expected_dy));
+        assert_eq!(position.alignment, alignment);
+    }
+
+    #[rstest]
+    #[case::bottom_start(
+        Placement::Bottom,
+        Alignment::Start,
+        "tour-tooltip-bottom-start",
+        "top"
+    )]
+    #[case::bottom_center(
+        Placement::Bottom,
+        Alignment::Center,
+        "tour-tooltip-bottom-center",
+        "top-middle"
+    )]
+    #[case::bottom_end(
+        Placement::Bottom,
+        Alignment::End,
+        "tour-tooltip-bottom-end",
+        "top-right"
+    )]
+    #[case::top_start(Placement::Top, Alignment::Start, "tour-tooltip-top-start", "bottom")]
+    #[case::top_end(Placement::Top, Alignment::End, "tour-tooltip-top-end", "bottom-right")]
+    #[case::right_start(Placement::Right, Alignment::Start, "tour-tooltip-right-start", "left")]
+    #[case::right_end(
+        Placement::Right,
+        Alignment::End,
+        "tour-tooltip-right-end",
+        "left-bottom"
+    )]
+    #[case::left_end(
+        Placement::Left,
+        Alignment::End,
+        "tour-tooltip-left-end",
+        "right-bottom"
+    )]
+    fn test_classes(
+        #[case] placement: Placement,
+        #[case] alignment: Alignment,
+        #[case] tooltip_class: &str,
+        #[case] arrow_class: &str,
+    ) {
+        let position = TooltipPosition {
+            placement,
+            alignment,
+            x: 0,
+            y: 0,
+            arrow_offset: None,
+            max_width: None,
+            max_height: None,
+        };
+        assert_eq!(position.tooltip_class(), tooltip_class);
+        assert_eq!(position.arrow_class(), arrow_class);
+    }
+
+    #[test]
+    fn test_arrow_offset_stays_on_tooltip() {
+        let rect = Rect {
+            x: 0,
+            y: 300,
+            width: 10,
+            height: 10,
+        };
+        let position = compute_position(
+            &rect,
+            Placement::Bottom,
+            Alignment::Center,
+            (TOOLTIP_WIDTH, TOOLTIP_HEIGHT),
+            &VIEWPORT,
+            &Middleware::defaults(),
+        );
+        assert_eq!((position.x, position.y), (0, 320));
+        assert_eq!(position.arrow_offset, Some(ARROW_SIZE));
+    }
+
+    fn compute(placement: Placement, rect: Rect, middleware: &[Middleware]) -> TooltipPosition {
+        compute_position(
+            &rect,
+            placement,
+            Alignment::Center,
+            (TOOLTIP_WIDTH, TOOLTIP_HEIGHT),
+            &VIEWPORT,
+            middleware,
+        )
+    }
+
+    #[rstest]
+    #[case::none(&[], Placement::Top, 350, 150)]
+    #[case::offset(&[Middleware::Offset(20)], Placement::Top, 350, 130)]
+    #[case::offsets_add_up(&[Middleware::Offset(5), Middleware::Offset(15)], Placement::Top, 350, 130)]
+    #[case::flip(&[Middleware::Flip], Placement::Top, 350, 150)]
+    #[case::flip_with_offset(&[Middleware::Offset(160), Middleware::Flip], Placement::Bottom, 350, 460)]
+    #[case::offset_after_flip(&[Middleware::Flip, Middleware::Offset(160)], Placement::Top, 350, -10)]
+    #[case::shift(&[Middleware::Offset(160), Middleware::Shift { padding: 0 }], Placement::Top, 350, 0)]
+    #[case::shift_with_padding(&[Middleware::Offset(160), Middleware::Shift { padding: 8 }], Placement::Top, 350, 8)]
+    fn test_middleware(
+        #[case] middleware: &[Middleware],
+        #[case] expected_placement: Placement,
+        #[case] expected_dx: i32,
+        #[case] expected_dy: i32,
+    ) {
+        let rect = Rect {
+            x: 350,
+            y: 250,
+            width: 100,
+            height: 50,
+        };
+        let position = compute(Placement::Top, rect, middleware);
+        assert_eq!(position.placement, expected_placement);
+        assert_eq!((position.x, position.y), (expected_dx, expected_dy));
+        assert_eq!(position.arrow_offset, None);
+        assert_eq!((position.max_width, position.max_height), (None, None));
+    }
+
+    #[test]
+    fn test_auto_considers_offsets() {
+        let rect = Rect {
+            x: 0,
+            y: 0,
+            width: 690,
+            height: 550,
+        };
+        // Without offset, the tooltip fits right of the element
+        assert_eq!(
+            compute(Placement::Auto, rect, &[]).placement,
+            Placement::Right
+        );
+        // With the offset it fits nowhere and goes below the element
+        let position = compute(Placement::Auto, rect, &[Middleware::Offset(20)]);
+        assert_eq!(position.placement, Placement::Bottom);
+        assert_eq!((position.x, position.y), (295, 570));
+    }
+
+    #[rstest]
+    #[case::below(Placement::Bottom, Rect { x: 350, y: 500, width: 100, height: 50 }, (100, 30), (780, 30), (350, 560))]
+    #[case::above(Placement::Top, Rect { x: 350, y: 50, width: 100, height: 50 }, (100, 30), (780, 30), (350, 10))]
+    #[case::beside(Placement::Right, Rect { x: 600, y: 250, width: 100, height: 50 }, (80, 100), (80, 580), (710, 225))]
+    #[case::enough_space(Placement::Bottom, Rect { x: 350, y: 100, width: 100, height: 50 }, (100, 100), (780, 430), (350, 160))]
+    fn test_size(
+        #[case] placement: Placement,
+        #[case] rect: Rect,
+        #[case] expected_size: (i32, i32),
+        #[case] expected_max_size: (i32, i32),
+        #[case] expected_position: (i32, i32),
+    ) {
+        let middleware = [Middleware::Offset(10), Middleware::Size { padding: 10 }];
+        let position = compute(placement, rect, &middleware);
+        assert_eq!(position.placement, placement);
+        assert_eq!(
+            (position.max_width, position.max_height),
+            (Some(expected_max_size.0), Some(expected_max_size.1))
+        );
+        assert_eq!((position.x, position.y), expected_position);
+
+        // The arrow stays on the shrunk tooltip
+        let position = compute(
+            placement,
+            rect,
+            &[
+                Middleware::Offset(10),
+                Middleware::Size { padding: 10 },
+                Middleware::Arrow { padding: 0 },
+            ],
+        );
+        let size = match placement {
+            Placement::Left | Placement::Right => expected_size.1,
+            _ => expected_size.0,
+        };
+        assert!(position.arrow_offset.unwrap() <= size);
+    }
+
+    #[rstest]
+    #[case::centered(Rect { x: 350, y: 250, width: 100, height: 50 }, 0, 50)]
+    #[case::padding(Rect { x: 0, y: 250, width: 10, height: 50 }, 20, 20)]
+    #[case::no_padding(Rect { x: 0, y: 250, width: 10, height: 50 }, 0, 5)]
+    fn test_arrow(#[case] rect: Rect, #[case] padding: i32, #[case] expected_arrow_offset: i32) {
+        let position = compute(
+            Placement::Bottom,
+            rect,
+            &[
+                Middleware::Offset(ARROW_SIZE),
+                Middleware::Shift { padding: 0 },
+                Middleware::Arrow { padding },
+            ],
+        );
+        assert_eq!(position.arrow_offset, Some(expected_arrow_offset));
+    }
+
+    #[rstest]
+    #[case::auto_top(Placement::Auto, Rect { x: 200, y: 500, width: 100, height: 50 })]
+    #[case::flipped(Placement::Top, Rect { x: 700, y: 20, width: 80, height: 50 })]
+    #[case::shifted(Placement::Bottom, Rect { x: 0, y: 250, width: 10, height: 50 })]
+    #[case::sized(Placement::Right, Rect { x: 650, y: 550, width: 100, height: 40 })]
+    fn test_scrolled_page(#[case] placement: Placement, #[case] rect: Rect) {
+        // Targets are in document coordinates, the viewport moves along when scrolling
+        let (scroll_x, scroll_y) = (300, 2000);
+        let middleware = [
+            Middleware::Offset(ARROW_SIZE),
+            Middleware::Flip,
+            Middleware::Size { padding: 5 },
+            Middleware::Shift { padding: 5 },
+            Middleware::Arrow { padding: 10 },
+        ];
+        let expected = compute(placement, rect, &middleware);
+        let scrolled = compute_position(
+            &Rect {
+                x: rect.x + scroll_x,
+                y: rect.y + scroll_y,
+                ..rect
+            },
+            placement,
+            Alignment::Center,
+            (TOOLTIP_WIDTH, TOOLTIP_HEIGHT),
+            &Rect {
+                x: scroll_x,
+                y: scroll_y,
+                ..VIEWPORT
+            },
+            &middleware,
+        );
+        assert_eq!(
+            scrolled,
+            TooltipPosition {
+                x: expected.x + scroll_x,
+                y: expected.y + scroll_y,
+                ..expected
+            }
+        );
     }
 }
@@ -0,0 +1,21 @@
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::StorageEvent;
+
+/// Listens for changes of the browser storage made by other tabs of the same origin.
+///
+/// The callback is called with the changed key. Clearing the whole storage is not
+/// reported. The subscription ends when the listener is dropped.
+pub fn listen_storage_changes(
+    callback: impl Fn(String) + 'static,
+) -> Result<EventListener, String> {
+    let window = web_sys::window().ok_or_else(|| "Failed to get window".to_string())?;
+    Ok(EventListener::new(&window, "storage", move |event| {
+        let Some(event) = event.dyn_ref::<StorageEvent>() else {
+            return;
+        };
+        if let Some(key) = event.key() {
+            callback(key);
+        }
+    }))
+}